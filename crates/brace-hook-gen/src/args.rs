@@ -1,10 +1,33 @@
 use proc_macro2::Span;
-use syn::parse::{Parse, ParseStream, Result};
-use syn::{LitInt, Path, Token};
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::token::Paren;
+use syn::{parenthesized, Expr, ExprLit, ExprUnary, Ident, Lit, LitInt, LitStr, Path, Token, UnOp};
+
+const PHASES: &[&str] = &["Early", "Normal", "Late"];
+
+const REGISTRATION_FLAGS: &[&str] = &["around", "default"];
+
+const REGISTRATION_LISTS: &[&str] = &["tags"];
 
 pub enum Args {
     None,
-    With(Path, LitInt),
+    With(Box<Registration>),
+}
+
+pub struct Registration {
+    pub paths: Vec<Path>,
+    pub weight: Expr,
+    pub phase: Option<Ident>,
+    pub default: bool,
+    pub name: Option<LitStr>,
+    pub tags: Vec<LitStr>,
+    pub after: Vec<Path>,
+    pub before: Vec<Path>,
+    pub replaces: Vec<Path>,
+    pub disables: Vec<Path>,
+    pub around: Option<Ident>,
 }
 
 impl Parse for Args {
@@ -12,17 +35,171 @@ impl Parse for Args {
         if input.is_empty() {
             Ok(Args::None)
         } else {
-            let path = input.parse::<Path>()?;
+            Ok(Args::With(Box::new(input.parse()?)))
+        }
+    }
+}
+
+impl Parse for Registration {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut paths = vec![input.parse::<Path>()?];
+        let mut weight = None;
+        let mut phase = None;
+        let mut default = None;
+        let mut name = None;
+        let mut tags = Vec::new();
+        let mut after = Vec::new();
+        let mut before = Vec::new();
+        let mut replaces = Vec::new();
+        let mut disables = Vec::new();
+        let mut around = None;
+
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
 
             if input.is_empty() {
-                return Ok(Self::With(path, LitInt::new("0", Span::call_site())));
+                break;
             }
 
-            input.parse::<Token![,]>()?;
+            if !is_keyword(input) {
+                match input.parse::<Expr>()? {
+                    Expr::Path(expr) if expr.qself.is_none() && expr.attrs.is_empty() => {
+                        paths.push(expr.path);
+                    }
+                    expr if is_literal_weight(&expr) => set_once(&mut weight, expr, "weight")?,
+                    expr => {
+                        return Err(Error::new_spanned(
+                            expr,
+                            "positional hook weights must be integer literals, \
+                             use `weight = ...` for constant expressions",
+                        ))
+                    }
+                }
+
+                continue;
+            }
+
+            let key = input.parse::<Ident>()?;
+
+            if key == "around" {
+                set_once(&mut around, key, "around")?;
+            } else if key == "default" {
+                set_once(&mut default, key, "default")?;
+            } else if key == "tags" {
+                let content;
+
+                parenthesized!(content in input);
+
+                tags.extend(Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?);
+            } else if key == "weight" {
+                input.parse::<Token![=]>()?;
+
+                set_once(&mut weight, input.parse::<Expr>()?, "weight")?;
+            } else if key == "phase" {
+                input.parse::<Token![=]>()?;
+
+                let ident = input.parse::<Ident>()?;
 
-            let weight = input.parse::<LitInt>()?;
+                if !PHASES.iter().any(|phase| ident == phase) {
+                    return Err(Error::new(
+                        ident.span(),
+                        format!("unknown hook phase `{}`", ident),
+                    ));
+                }
 
-            Ok(Self::With(path, weight))
+                set_once(&mut phase, ident, "phase")?;
+            } else if key == "name" {
+                input.parse::<Token![=]>()?;
+
+                set_once(&mut name, input.parse::<LitStr>()?, "name")?;
+            } else if key == "after" {
+                input.parse::<Token![=]>()?;
+
+                after.push(input.parse()?);
+            } else if key == "before" {
+                input.parse::<Token![=]>()?;
+
+                before.push(input.parse()?);
+            } else if key == "replaces" {
+                input.parse::<Token![=]>()?;
+
+                replaces.push(input.parse()?);
+            } else if key == "disables" {
+                input.parse::<Token![=]>()?;
+
+                disables.push(input.parse()?);
+            } else {
+                return Err(Error::new(
+                    key.span(),
+                    format!("unknown hook argument `{}`", key),
+                ));
+            }
+        }
+
+        Ok(Self {
+            paths,
+            weight: weight.unwrap_or_else(|| {
+                Expr::Lit(ExprLit {
+                    attrs: Vec::new(),
+                    lit: Lit::Int(LitInt::new("0", Span::call_site())),
+                })
+            }),
+            phase,
+            default: default.is_some(),
+            name,
+            tags,
+            after,
+            before,
+            replaces,
+            disables,
+            around,
+        })
+    }
+}
+
+fn is_keyword(input: ParseStream) -> bool {
+    let fork = input.fork();
+
+    // Anything that is not a known key is a positional hook path or weight.
+    match fork.parse::<Ident>() {
+        Ok(ident) if fork.is_empty() || fork.peek(Token![,]) => {
+            REGISTRATION_FLAGS.iter().any(|flag| ident == flag)
         }
+        Ok(ident) if fork.peek(Paren) => REGISTRATION_LISTS.iter().any(|list| ident == list),
+        Ok(_) => fork.peek(Token![=]) && !fork.peek(Token![==]),
+        Err(_) => false,
+    }
+}
+
+fn is_literal_weight(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(_), ..
+        }) => true,
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => matches!(
+            expr.as_ref(),
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(_),
+                ..
+            })
+        ),
+        _ => false,
     }
 }
+
+fn set_once<T: Spanned>(slot: &mut Option<T>, value: T, key: &str) -> Result<()> {
+    if slot.is_some() {
+        return Err(Error::new(
+            value.span(),
+            format!("duplicate hook argument `{}`", key),
+        ));
+    }
+
+    *slot = Some(value);
+
+    Ok(())
+}
@@ -1,10 +1,62 @@
-use syn::parse::{Error, ParseStream, Result};
-use syn::{Attribute, Path, Token};
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Path, Token};
 
-// #[hook_attr(crate = brace_hook_crate)]
-// See https://github.com/dtolnay/inventory/issues/10
-pub fn crate_path(attrs: &mut Vec<Attribute>, krate: Path) -> Result<Path> {
-    let mut path = None;
+pub struct HookAttrs {
+    pub krate: Path,
+    pub local: Option<Ident>,
+    pub cache: Option<Ident>,
+    pub pipeline: Option<Ident>,
+    pub alterable: Option<Ident>,
+}
+
+enum HookAttr {
+    // #[hook_attr(crate = brace_hook_crate)]
+    // See https://github.com/dtolnay/inventory/issues/10
+    Crate(Path),
+    // #[hook_attr(local)]
+    Local(Ident),
+    // #[hook_attr(cache)]
+    Cache(Ident),
+    // #[hook_attr(pipeline)]
+    Pipeline(Ident),
+    // #[hook_attr(alterable)]
+    Alterable(Ident),
+}
+
+impl Parse for HookAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![crate]) {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+
+            return Ok(HookAttr::Crate(input.call(Path::parse_mod_style)?));
+        }
+
+        let key = input.parse::<Ident>()?;
+
+        if key == "local" {
+            Ok(HookAttr::Local(key))
+        } else if key == "cache" {
+            Ok(HookAttr::Cache(key))
+        } else if key == "pipeline" {
+            Ok(HookAttr::Pipeline(key))
+        } else if key == "alterable" {
+            Ok(HookAttr::Alterable(key))
+        } else {
+            Err(Error::new(key.span(), "unknown hook attribute"))
+        }
+    }
+}
+
+pub fn hook_attrs(attrs: &mut Vec<Attribute>, krate: Path) -> Result<HookAttrs> {
+    let mut res = HookAttrs {
+        krate,
+        local: None,
+        cache: None,
+        pipeline: None,
+        alterable: None,
+    };
     let mut errors: Option<Error> = None;
 
     attrs.retain(|attr| {
@@ -12,12 +64,18 @@ pub fn crate_path(attrs: &mut Vec<Attribute>, krate: Path) -> Result<Path> {
             return true;
         }
 
-        match attr.parse_args_with(|input: ParseStream| {
-            input.parse::<Token![crate]>()?;
-            input.parse::<Token![=]>()?;
-            input.call(Path::parse_mod_style)
-        }) {
-            Ok(res) => path = Some(res),
+        match attr.parse_args_with(Punctuated::<HookAttr, Token![,]>::parse_terminated) {
+            Ok(items) => {
+                for item in items {
+                    match item {
+                        HookAttr::Crate(path) => res.krate = path,
+                        HookAttr::Local(ident) => res.local = Some(ident),
+                        HookAttr::Cache(ident) => res.cache = Some(ident),
+                        HookAttr::Pipeline(ident) => res.pipeline = Some(ident),
+                        HookAttr::Alterable(ident) => res.alterable = Some(ident),
+                    }
+                }
+            }
             Err(err) => match &mut errors {
                 None => errors = Some(err),
                 Some(errors) => errors.combine(err),
@@ -28,7 +86,25 @@ pub fn crate_path(attrs: &mut Vec<Attribute>, krate: Path) -> Result<Path> {
     });
 
     match errors {
-        None => Ok(path.unwrap_or(krate)),
+        None => Ok(res),
         Some(errors) => Err(errors),
     }
 }
+
+pub fn crate_path(attrs: &mut Vec<Attribute>, krate: Path) -> Result<Path> {
+    let attrs = hook_attrs(attrs, krate)?;
+
+    let declaration_only = attrs
+        .local
+        .or(attrs.cache)
+        .or(attrs.pipeline)
+        .or(attrs.alterable);
+
+    match declaration_only {
+        Some(ident) => Err(Error::new(
+            ident.span(),
+            format!("`{}` is only valid on hook declarations", ident),
+        )),
+        None => Ok(attrs.krate),
+    }
+}
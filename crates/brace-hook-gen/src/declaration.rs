@@ -1,15 +1,18 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::Path;
+use syn::{Error, Pat, Path};
 
+use crate::render::render;
 use crate::signature::HookFnSignature;
 
 pub fn expand(krate: Path, mut input: HookFnSignature) -> TokenStream {
-    let krate = match crate::attr::crate_path(&mut input.attrs, krate) {
-        Ok(path) => path,
+    let attrs = match crate::attr::hook_attrs(&mut input.attrs, krate) {
+        Ok(attrs) => attrs,
         Err(err) => return err.to_compile_error(),
     };
 
+    let krate = attrs.krate;
+
     let arg_names_tuple = match input.arg_names_tuple() {
         Ok(res) => res,
         Err(err) => return err.to_compile_error(),
@@ -35,24 +38,413 @@ pub fn expand(krate: Path, mut input: HookFnSignature) -> TokenStream {
         Err(err) => return err.to_compile_error(),
     };
 
-    let iter_arg_names = input.iter_arg_names();
+    let mut iter_arg_names = input.iter_arg_names();
 
     let ret = input.returns();
+    let name = input.ident.clone();
+    let default_name = format_ident!("{}__default", name);
+    let iter_name = format_ident!("{}__iter", name);
+    let scope_name = format_ident!("{}__scope", name);
+    let kind_name = format_ident!("{}__kind", name);
+
+    let bounds = match attrs.local {
+        Some(_) => quote!(),
+        None => quote!(+ Send + Sync),
+    };
+
+    let (hook_fn, item, default_fn) = match input.asyncness {
+        Some(_) => {
+            if let Some(ty) = input.mut_arg() {
+                return Error::new_spanned(ty, "async hooks cannot take `&mut` arguments")
+                    .to_compile_error();
+            }
+
+            let arg_types = match input.arg_types_lifetimes() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let future_lifetime = match input.future_lifetime() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let send = match attrs.local {
+                Some(_) => quote!(),
+                None => quote!(+ Send),
+            };
+
+            let item = quote! {
+                std::pin::Pin<Box<dyn std::future::Future<Output = #ret> #send + #future_lifetime>>
+            };
+
+            let names = (0..input.inputs.len())
+                .map(|pos| format_ident!("arg{}", pos))
+                .collect::<Vec<_>>();
+
+            let binder = if lifetimes.params.is_empty() {
+                quote!()
+            } else {
+                quote!(for #lifetimes)
+            };
+
+            (
+                quote!(#binder Fn(#arg_types) -> #item),
+                item,
+                quote!(|#(#names),*| Box::pin(#default_name(#(#names),*))),
+            )
+        }
+        None => {
+            let output = &input.output;
+
+            (
+                quote!(Fn(#arg_types) #output),
+                quote!(#ret),
+                quote!(#default_name),
+            )
+        }
+    };
+
+    // Around implementations share the registry with the regular ones, so hooks
+    // that support them store either kind of implementation.
+    let around_types = match (input.asyncness, input.mut_arg()) {
+        (None, None) => Some(arg_types.iter().collect::<Vec<_>>()),
+        _ => None,
+    };
+
+    let (hook_ty, wrap) = match around_types {
+        Some(_) => (quote!(#kind_name), quote!(#kind_name::Call)),
+        None => (quote!(Box<dyn #hook_fn #bounds>), quote!()),
+    };
+
+    let call = |hook: TokenStream, args: TokenStream| match around_types {
+        Some(_) => quote! {
+            match &#hook.hook {
+                #kind_name::Call(hook) => hook(#args),
+                #kind_name::Around(_) => unreachable!("around implementations only run in a chain"),
+            }
+        },
+        None => quote!((#hook.hook)(#args)),
+    };
+
+    let dispatch = match (input.asyncness, input.control_flow()) {
+        (None, Some(brk)) => {
+            let arg_names = match input.arg_names() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            quote! {
+                pub fn dispatch #lifetimes (#args_lifetimes) -> Option<(std::sync::Arc<#krate::HookInfo>, #brk)> {
+                    #krate::hook::dispatch(Self::with(#arg_names))
+                }
+            }
+        }
+        _ => quote!(),
+    };
+
+    let pipeline = match attrs.pipeline {
+        Some(flag) => {
+            if let Some(asyncness) = input.asyncness {
+                return Error::new_spanned(asyncness, "pipeline hooks cannot be async")
+                    .to_compile_error();
+            }
+
+            let arg_names = match input.arg_names() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let value = match arg_names.first() {
+                Some(value) => value,
+                None => {
+                    return Error::new_spanned(
+                        flag,
+                        "pipeline hooks must take the piped value as their first argument",
+                    )
+                    .to_compile_error()
+                }
+            };
+
+            if let Some(arg) = iter_arg_names.first_mut() {
+                *arg = Pat::Verbatim(quote!(std::clone::Clone::clone(&self.args.0)));
+            }
+
+            let call = call(quote!(__hook), quote!(#arg_names));
+
+            quote! {
+                pub fn pipeline #lifetimes (#args_lifetimes) -> #ret {
+                    Self::hooks()
+                        .iter()
+                        .fold(#value, |#value, __hook| #call)
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    let call_iter = call(quote!(hook), quote!(#iter_arg_names));
+
+    let parallel = match (input.asyncness, input.mut_arg()) {
+        (None, None) => quote! {
+            impl #lifetimes #krate::ParallelHookIterator for #iter_name #lifetimes {
+                fn call(&self, pos: usize) -> Self::Item {
+                    match self.hooks.get(pos) {
+                        Some(hook) => #call_iter,
+                        None => panic!("hook index out of bounds: {}", pos),
+                    }
+                }
+            }
+        },
+        _ => quote!(),
+    };
+
+    let alter = match attrs.alterable {
+        Some(_) => {
+            if let Some(asyncness) = input.asyncness {
+                return Error::new_spanned(asyncness, "alterable hooks cannot be async")
+                    .to_compile_error();
+            }
+
+            let arg_names = match input.arg_names() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let alter_name = format_ident!("{}_alter", name);
+            let alter_attrs = input.attrs.iter().filter(|attr| !attr.path.is_ident("doc"));
+            let local = attrs.local.as_ref().map(|_| quote!(#[hook_attr(local)]));
+            let vis = &input.vis;
+
+            let alter = match syn::parse2(quote! {
+                #(#alter_attrs)*
+                #[hook_attr(crate = #krate)]
+                #local
+                #vis fn #alter_name(value: &mut #ret) {}
+            }) {
+                Ok(sig) => expand(krate.clone(), sig),
+                Err(err) => return err.to_compile_error(),
+            };
+
+            quote! {
+                impl #name {
+                    pub fn collect #lifetimes (#args_lifetimes) -> #ret {
+                        let mut res = match #krate::hook::merge(
+                            Self::with(#arg_names),
+                            #krate::Conflict::LastWins,
+                        ) {
+                            Ok(res) => res,
+                            Err(err) => panic!(
+                                "{} to merge `{}` with `Conflict::LastWins`",
+                                err,
+                                stringify!(#ret),
+                            ),
+                        };
+
+                        #krate::hook::exec(#alter_name::with(&mut res));
+
+                        res
+                    }
+                }
+
+                #alter
+            }
+        }
+        None => quote!(),
+    };
+
+    let cache = match attrs.cache {
+        Some(_) => {
+            if let Some(asyncness) = input.asyncness {
+                return Error::new_spanned(asyncness, "cached hooks cannot be async")
+                    .to_compile_error();
+            }
+
+            if let Some(ty) = input.borrowed_arg() {
+                return Error::new_spanned(
+                    ty,
+                    "cached hook arguments must be owned `'static + Hash + Eq`",
+                )
+                .to_compile_error();
+            }
+
+            let arg_names = match input.arg_names() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let args = &input.inputs;
+            let key_types = arg_types.iter().map(|arg| &arg.ty).collect::<Vec<_>>();
+
+            quote! {
+                impl #name {
+                    pub fn cached(#args) -> std::sync::Arc<Vec<#ret>> {
+                        Self::cache().get_or_insert_with(#arg_names_tuple, || {
+                            Self::with(#arg_names).collect()
+                        })
+                    }
+
+                    pub fn invalidate() {
+                        Self::cache().invalidate();
+                    }
+
+                    fn cache() -> &'static #krate::Cache<(#(#key_types,)*), Vec<#ret>> {
+                        static CACHE: #krate::Cache<(#(#key_types,)*), Vec<#ret>> = #krate::Cache::new();
+
+                        &CACHE
+                    }
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    // Local hooks are not `Sync`, so every thread resolves them in a registry of
+    // its own, dropped with the thread, and runtime registration, which is
+    // shared between threads, is left out.
+    let (registry, register) = match attrs.local {
+        Some(_) => (
+            quote! {
+                fn with_registry<F, R>(f: F) -> R
+                where
+                    F: FnOnce(&#krate::Registry<#name>) -> R,
+                {
+                    std::thread_local! {
+                        static HOOKS: #krate::Registry<#name> = #krate::Registry::new();
+                    }
+
+                    HOOKS.with(f)
+                }
+            },
+            quote!(),
+        ),
+        None => (
+            quote! {
+                fn with_registry<F, R>(f: F) -> R
+                where
+                    F: FnOnce(&#krate::Registry<#name>) -> R,
+                {
+                    f(Self::registry())
+                }
+
+                fn registry() -> &'static #krate::Registry<#name> {
+                    static HOOKS: #krate::Registry<#name> = #krate::Registry::new();
+
+                    &HOOKS
+                }
+            },
+            quote! {
+                pub fn register<T>(hook: T, weight: i32) -> #krate::RegistrationHandle
+                where
+                    T: #hook_fn + Send + Sync + 'static,
+                {
+                    let info = #krate::HookInfo::new(std::any::type_name::<T>(), weight, false);
+
+                    Self::register_with_info(hook, info)
+                }
+
+                pub fn register_with_info<T>(
+                    hook: T,
+                    info: #krate::HookInfo,
+                ) -> #krate::RegistrationHandle
+                where
+                    T: #hook_fn + Send + Sync + 'static,
+                {
+                    Self::registry().register(Self::with_info(hook, info))
+                }
+            },
+        ),
+    };
+
+    let around = match &around_types {
+        Some(types) => {
+            let arg_names = match input.arg_names() {
+                Ok(res) => res,
+                Err(err) => return err.to_compile_error(),
+            };
+
+            let names = iter_arg_names.iter();
+
+            quote! {
+                impl #name {
+                    pub fn around<T>(hook: T, info: #krate::HookInfo) -> Self
+                    where
+                        T: Fn(#(#types,)* #krate::Next<'_, #ret>) -> #ret #bounds + 'static,
+                    {
+                        Self {
+                            hook: #kind_name::Around(Box::new(hook)),
+                            info: std::sync::Arc::new(info),
+                        }
+                    }
+
+                    pub fn handle #lifetimes (#args_lifetimes) -> Option<#ret> {
+                        Self::with(#arg_names).handle()
+                    }
+                }
+
+                #[allow(non_camel_case_types)]
+                enum #kind_name {
+                    Call(Box<dyn #hook_fn #bounds>),
+                    Around(Box<dyn Fn(#(#types,)* #krate::Next<'_, #ret>) -> #ret #bounds>),
+                }
+
+                impl #lifetimes #iter_name #lifetimes {
+                    pub fn handle(self) -> Option<#ret> {
+                        let chain = #name::chain();
+
+                        self.run_chain(&chain, 0)
+                    }
+
+                    // Runs the chain in weight order. Every regular implementation
+                    // runs and the last result wins, while an around
+                    // implementation decides whether and how often the rest of
+                    // the chain runs through `next`.
+                    fn run_chain(&self, chain: &#krate::Hooks<#name>, pos: usize) -> Option<#ret> {
+                        match &chain.get(pos)?.hook {
+                            #kind_name::Around(hook) => Some(hook(
+                                #(#names,)*
+                                #krate::Next::new(&|| self.run_chain(chain, pos + 1)),
+                            )),
+                            #kind_name::Call(hook) => {
+                                let res = hook(#iter_arg_names);
+
+                                self.run_chain(chain, pos + 1).or(Some(res))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        None => quote!(),
+    };
+
+    let is_around = match around_types {
+        Some(_) => quote! {
+            fn is_around(&self) -> bool {
+                matches!(self.hook, #kind_name::Around(_))
+            }
+        },
+        None => quote!(),
+    };
+
     let vis = input.vis;
+    let asyncness = input.asyncness;
     let args = input.inputs;
     let output = input.output;
-    let name = input.ident;
-    let default_name = format_ident!("{}__default", name);
-    let iter_name = format_ident!("{}__iter", name);
+    let signature = render(quote!(#asyncness fn #name(#args) #output));
 
     let default = match input.block {
         Some(block) => quote! {
             #[allow(unused_variables, non_snake_case)]
-            fn #default_name(#args) -> #ret #block
+            #asyncness fn #default_name(#args) -> #ret #block
 
             #krate::inventory::submit! {
                 #![crate = #krate]
-                #name::new(#default_name, 0, true)
+                #name::with_info(
+                    #default_fn,
+                    #krate::HookInfo::new(std::any::type_name_of_val(&#default_name), 0, true)
+                        .with_location(module_path!(), file!(), line!()),
+                )
             }
         },
         None => quote!(),
@@ -60,68 +452,175 @@ pub fn expand(krate: Path, mut input: HookFnSignature) -> TokenStream {
 
     quote! {
         #[allow(non_camel_case_types)]
-        #vis struct #name(Box<dyn Fn(#arg_types) #output>, i32, bool);
+        #vis struct #name {
+            hook: #hook_ty,
+            info: std::sync::Arc<#krate::HookInfo>,
+        }
 
         impl #name {
             pub fn new<T>(hook: T, weight: i32, default: bool) -> Self
             where
-                T: Fn(#arg_types) #output + 'static,
+                T: #hook_fn #bounds + 'static,
             {
-                Self(Box::new(hook), weight, default)
+                let info = #krate::HookInfo::new(std::any::type_name::<T>(), weight, default);
+
+                Self::with_info(hook, info)
             }
 
-            pub fn with #lifetimes (#args_lifetimes) -> #iter_name #lifetimes {
-                let mut hooks: Vec<&'static #name> = #krate::inventory::iter::<#name>
+            pub fn with_info<T>(hook: T, info: #krate::HookInfo) -> Self
+            where
+                T: #hook_fn #bounds + 'static,
+            {
+                Self {
+                    hook: #wrap(Box::new(hook)),
+                    info: std::sync::Arc::new(info),
+                }
+            }
+
+            pub fn info(&self) -> &#krate::HookInfo {
+                &self.info
+            }
+
+            pub fn implementations() -> impl Iterator<Item = std::sync::Arc<#krate::HookInfo>> {
+                let hooks = Self::chain();
+
+                hooks.iter().map(|hook| hook.info.clone()).collect::<Vec<_>>().into_iter()
+            }
+
+            pub fn registered() -> impl Iterator<Item = (std::sync::Arc<#krate::HookInfo>, bool)> {
+                let hooks = Self::with_registry(|registry| registry.registered());
+                let chain = Self::chain();
+
+                hooks
+                    .iter()
+                    .map(|hook| {
+                        let active = chain.iter().any(|other| std::ptr::eq(hook, other));
+
+                        (hook.info.clone(), active)
+                    })
+                    .collect::<Vec<_>>()
                     .into_iter()
-                    .filter(|hook| !hook.2)
-                    .collect();
+            }
 
-                if hooks.is_empty() {
-                    hooks = #krate::inventory::iter::<#name>
-                        .into_iter()
-                        .filter(|hook| hook.2)
-                        .collect();
-                }
+            #register
+
+            pub fn scope() -> #scope_name {
+                #scope_name(#krate::Scope::new())
+            }
 
-                hooks.sort_by_key(|hook| hook.1);
+            pub fn override_scope<F, R>(f: F) -> R
+            where
+                F: FnOnce(&mut #scope_name) -> R,
+            {
+                f(&mut Self::scope())
+            }
 
+            pub fn with #lifetimes (#args_lifetimes) -> #iter_name #lifetimes {
                 #iter_name {
                     args: #arg_names_tuple,
-                    hooks: hooks.into_iter(),
+                    hooks: Self::hooks(),
                 }
             }
+
+            #dispatch
+
+            #pipeline
+
+            fn hooks() -> #krate::Hooks<#name> {
+                Self::with_registry(|registry| registry.hooks(stringify!(#name)))
+            }
+
+            fn chain() -> #krate::Hooks<#name> {
+                Self::with_registry(|registry| registry.chain(stringify!(#name)))
+            }
+
+            #registry
+        }
+
+        impl #krate::Implementation for #name {
+            fn info(&self) -> &#krate::HookInfo {
+                &self.info
+            }
+
+            #is_around
+        }
+
+        #[allow(non_camel_case_types)]
+        #vis struct #scope_name(#krate::Scope<#name>);
+
+        impl #scope_name {
+            pub fn disable<N>(&mut self, name: N) -> &mut Self
+            where
+                N: Into<String>,
+            {
+                self.0.disable(name);
+                self
+            }
+
+            pub fn add<T>(&mut self, hook: T, weight: i32) -> &mut Self
+            where
+                T: #hook_fn #bounds + 'static,
+            {
+                self.0.add(#name::new(hook, weight, false));
+                self
+            }
         }
 
         #[allow(non_camel_case_types)]
         #vis struct #iter_name #lifetimes {
             args: #iter_arg_types,
-            hooks: std::vec::IntoIter<&'static #name>,
+            hooks: #krate::Hooks<#name>,
         }
 
         impl #lifetimes std::iter::Iterator for #iter_name #lifetimes {
-            type Item = #ret;
+            type Item = #item;
 
             fn next(&mut self) -> Option<Self::Item> {
-                match self.hooks.next() {
-                    Some(hook) => Some(hook.0(#iter_arg_names)),
+                match self.hooks.pop_front() {
+                    Some(hook) => Some(#call_iter),
                     None => None,
                 }
             }
 
             fn size_hint(&self) -> (usize, Option<usize>) {
-                self.hooks.size_hint()
+                (self.hooks.len(), Some(self.hooks.len()))
             }
         }
 
         impl #lifetimes std::iter::DoubleEndedIterator for #iter_name #lifetimes {
             fn next_back(&mut self) -> Option<Self::Item> {
-                match self.hooks.next_back() {
-                    Some(hook) => Some(hook.0(#iter_arg_names)),
+                match self.hooks.pop_back() {
+                    Some(hook) => Some(#call_iter),
+                    None => None,
+                }
+            }
+        }
+
+        impl #lifetimes #krate::HookIterator for #iter_name #lifetimes {
+            fn hook(&self) -> &'static str {
+                stringify!(#name)
+            }
+
+            fn peek_info(&self) -> Option<&std::sync::Arc<#krate::HookInfo>> {
+                self.hooks.peek_front().map(|hook| &hook.info)
+            }
+
+            fn next_tagged(&mut self) -> Option<(std::sync::Arc<#krate::HookInfo>, Self::Item)> {
+                match self.hooks.pop_front() {
+                    Some(hook) => Some((hook.info.clone(), #call_iter)),
                     None => None,
                 }
             }
         }
 
+        #parallel
+
+        #around
+
+        #alter
+
+        #cache
+
         impl #lifetimes std::iter::ExactSizeIterator for #iter_name #lifetimes {}
 
         impl #lifetimes std::iter::FusedIterator for #iter_name #lifetimes {}
@@ -134,6 +633,17 @@ pub fn expand(krate: Path, mut input: HookFnSignature) -> TokenStream {
             }
         }
 
+        #krate::inventory::submit! {
+            #![crate = #krate]
+            #krate::HookDeclaration::new(
+                stringify!(#name),
+                module_path!(),
+                #signature,
+                || Box::new(#name::implementations()),
+                || Box::new(#name::registered()),
+            )
+        }
+
         #default
     }
 }
@@ -3,4 +3,5 @@ pub mod attr;
 pub mod declaration;
 pub mod lifetime;
 pub mod registration;
+pub mod render;
 pub mod signature;
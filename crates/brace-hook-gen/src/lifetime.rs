@@ -12,6 +12,7 @@ pub struct Lifetimes {
     pub elided: Vec<Lifetime>,
     pub explicit: Vec<Lifetime>,
     pub name: &'static str,
+    pub unified: bool,
 }
 
 impl Lifetimes {
@@ -20,6 +21,14 @@ impl Lifetimes {
             elided: Vec::new(),
             explicit: Vec::new(),
             name,
+            unified: false,
+        }
+    }
+
+    pub fn unified(name: &'static str) -> Self {
+        Lifetimes {
+            unified: true,
+            ..Lifetimes::new(name)
         }
     }
 
@@ -61,6 +70,12 @@ impl Lifetimes {
     }
 
     fn next_lifetime(&mut self) -> Lifetime {
+        if self.unified {
+            if let Some(life) = self.elided.first() {
+                return life.clone();
+            }
+        }
+
         let name = format!("{}{}", self.name, self.elided.len());
         let life = Lifetime::new(&name, Span::call_site());
         self.elided.push(life.clone());
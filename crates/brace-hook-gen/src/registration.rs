@@ -1,7 +1,11 @@
 use proc_macro2::TokenStream;
-use syn::{ItemFn, LitInt, Path};
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{Error, ItemFn, Path};
 
-pub fn expand(krate: Path, path: Path, weight: LitInt, mut input: ItemFn) -> TokenStream {
+use crate::args::Registration;
+
+pub fn expand(krate: Path, args: Registration, mut input: ItemFn) -> TokenStream {
     let name = input.sig.ident.clone();
 
     let krate = match crate::attr::crate_path(&mut input.attrs, krate) {
@@ -9,12 +13,69 @@ pub fn expand(krate: Path, path: Path, weight: LitInt, mut input: ItemFn) -> Tok
         Err(err) => return err.to_compile_error(),
     };
 
-    quote::quote! {
+    let weight = args.weight;
+    let weight = match args.phase {
+        Some(phase) => quote!(#krate::Phase::#phase.weight(#weight)),
+        None => quote!(#weight),
+    };
+    let weight = quote! {
+        {
+            const WEIGHT: i32 = #weight;
+            WEIGHT
+        }
+    };
+    let default = args.default;
+    let rename = args.name.map(|name| quote!(.with_name(#name)));
+    let tags = args.tags;
+    let after = args.after;
+    let before = args.before;
+    let replaces = args.replaces;
+    let disables = args.disables;
+
+    let ctor = match (args.around, input.sig.asyncness) {
+        (Some(_), Some(asyncness)) => {
+            return Error::new_spanned(asyncness, "around hooks cannot be async").to_compile_error()
+        }
+        (Some(_), None) => quote!(around),
+        (None, _) => quote!(with_info),
+    };
+
+    let hook = match input.sig.asyncness {
+        Some(_) => {
+            let names = (0..input.sig.inputs.len())
+                .map(|pos| format_ident!("arg{}", pos))
+                .collect::<Vec<_>>();
+
+            quote!(|#(#names),*| Box::pin(#name(#(#names),*)))
+        }
+        None => quote!(#name),
+    };
+
+    let info = quote! {
+        #krate::HookInfo::new(std::any::type_name_of_val(&#name), #weight, #default)
+            .with_location(module_path!(), file!(), line!())
+            #rename
+            #(.with_tag(#tags))*
+            #(.run_after(std::any::type_name_of_val(&#after)))*
+            #(.run_before(std::any::type_name_of_val(&#before)))*
+            #(.replace(std::any::type_name_of_val(&#replaces)))*
+            #(.disable(std::any::type_name_of_val(&#disables)))*
+    };
+
+    // Spanned to the target so that a path which is not a hook is reported
+    // where it was written.
+    let hooks = args
+        .paths
+        .iter()
+        .map(|path| quote_spanned!(path.span()=> #path::#ctor))
+        .collect::<Vec<_>>();
+
+    quote! {
         #input
 
-        #krate::inventory::submit! {
+        #(#krate::inventory::submit! {
             #![crate = #krate]
-            #path::new(#name, #weight, false)
-        }
+            #hooks(#hook, #info)
+        })*
     }
 }
@@ -0,0 +1,57 @@
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+
+pub fn render(tokens: TokenStream) -> String {
+    let mut out = String::new();
+    let mut prev: Option<TokenTree> = None;
+    let mut path = false;
+
+    for token in tokens {
+        if let Some(prev) = &prev {
+            if !path && spaced(prev, &token) {
+                out.push(' ');
+            }
+        }
+
+        path = is_punct(&token, ':') && prev.as_ref().is_some_and(|prev| is_punct(prev, ':'));
+
+        match &token {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+
+                out.push_str(open);
+                out.push_str(&render(group.stream()));
+                out.push_str(close);
+            }
+            token => out.push_str(&token.to_string()),
+        }
+
+        prev = Some(token);
+    }
+
+    out
+}
+
+fn spaced(prev: &TokenTree, next: &TokenTree) -> bool {
+    if let TokenTree::Punct(punct) = prev {
+        if punct.spacing() == Spacing::Joint || matches!(punct.as_char(), '&' | '<') {
+            return false;
+        }
+    }
+
+    match next {
+        TokenTree::Punct(punct) => !matches!(punct.as_char(), ',' | ';' | ':' | '<' | '>'),
+        TokenTree::Group(group) => {
+            group.delimiter() != Delimiter::Parenthesis || !matches!(prev, TokenTree::Ident(_))
+        }
+        _ => true,
+    }
+}
+
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch)
+}
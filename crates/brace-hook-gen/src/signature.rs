@@ -1,13 +1,15 @@
 use std::iter::FromIterator;
 
+use proc_macro2::Span;
 use quote::quote;
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
 use syn::token::{Comma, Paren};
 use syn::visit_mut::VisitMut;
 use syn::{
-    braced, parenthesized, Attribute, BareFnArg, Block, Expr, ExprTuple, FnArg, Generics, Ident,
-    Index, Pat, ReturnType, Token, Type, TypeTuple, Visibility,
+    braced, parenthesized, Attribute, BareFnArg, Block, Expr, ExprTuple, FnArg, GenericArgument,
+    Generics, Ident, Index, Lifetime, Pat, PathArguments, ReturnType, Token, Type, TypeTuple,
+    Visibility,
 };
 
 use crate::lifetime::Lifetimes;
@@ -15,6 +17,7 @@ use crate::lifetime::Lifetimes;
 pub struct HookFnSignature {
     pub attrs: Vec<Attribute>,
     pub vis: Visibility,
+    pub asyncness: Option<Token![async]>,
     pub fn_token: Token![fn],
     pub ident: Ident,
     pub paren_token: Paren,
@@ -80,9 +83,20 @@ impl HookFnSignature {
         Ok(args)
     }
 
+    pub fn arg_types_lifetimes(&self) -> Result<Punctuated<BareFnArg, Token![,]>> {
+        let mut args = self.arg_types()?;
+        let mut lifetimes = self.lifetimes_visitor();
+
+        for arg in args.iter_mut() {
+            lifetimes.visit_type_mut(&mut arg.ty);
+        }
+
+        Ok(args)
+    }
+
     pub fn args_lifetimes(&self) -> Result<Punctuated<FnArg, Token![,]>> {
         let mut args = self.inputs.clone();
-        let mut lifetimes = Lifetimes::new("'life");
+        let mut lifetimes = self.lifetimes_visitor();
 
         for arg in args.iter_mut() {
             if let FnArg::Typed(arg) = arg {
@@ -95,7 +109,7 @@ impl HookFnSignature {
 
     pub fn lifetimes(&self) -> Result<Generics> {
         let mut args = self.arg_types()?;
-        let mut lifetimes = Lifetimes::new("'life");
+        let mut lifetimes = self.lifetimes_visitor();
 
         for arg in args.iter_mut() {
             lifetimes.visit_type_mut(&mut arg.ty);
@@ -104,6 +118,20 @@ impl HookFnSignature {
         Ok(lifetimes.generics())
     }
 
+    pub fn future_lifetime(&self) -> Result<Lifetime> {
+        let mut args = self.arg_types()?;
+        let mut lifetimes = self.lifetimes_visitor();
+
+        for arg in args.iter_mut() {
+            lifetimes.visit_type_mut(&mut arg.ty);
+        }
+
+        Ok(match lifetimes.elided.first() {
+            Some(lifetime) => lifetime.clone(),
+            None => Lifetime::new("'static", Span::call_site()),
+        })
+    }
+
     pub fn iter_arg_names(&self) -> Punctuated<Pat, Token![,]> {
         let mut args = Punctuated::new();
 
@@ -121,7 +149,7 @@ impl HookFnSignature {
     pub fn iter_arg_types(&self) -> Result<Type> {
         let mut args = self.arg_types()?;
         let mut elems = Punctuated::new();
-        let mut lifetimes = Lifetimes::new("'life");
+        let mut lifetimes = self.lifetimes_visitor();
 
         for arg in args.iter_mut() {
             lifetimes.visit_type_mut(&mut arg.ty);
@@ -139,6 +167,55 @@ impl HookFnSignature {
         }))
     }
 
+    pub fn mut_arg(&self) -> Option<&Type> {
+        self.inputs.iter().find_map(|arg| match arg {
+            FnArg::Typed(pat) => match pat.ty.as_ref() {
+                Type::Reference(ty) if ty.mutability.is_some() => Some(pat.ty.as_ref()),
+                _ => None,
+            },
+            FnArg::Receiver(_) => None,
+        })
+    }
+
+    pub fn borrowed_arg(&self) -> Option<&Type> {
+        self.inputs.iter().find_map(|arg| match arg {
+            FnArg::Typed(pat) => {
+                let mut lifetimes = self.lifetimes_visitor();
+
+                lifetimes.visit_type_mut(&mut pat.ty.as_ref().clone());
+
+                lifetimes
+                    .elided
+                    .iter()
+                    .chain(&lifetimes.explicit)
+                    .any(|lifetime| lifetime.ident != "static")
+                    .then(|| pat.ty.as_ref())
+            }
+            FnArg::Receiver(_) => None,
+        })
+    }
+
+    pub fn control_flow(&self) -> Option<Type> {
+        let path = match self.returns() {
+            Type::Path(ty) if ty.qself.is_none() => ty.path,
+            _ => return None,
+        };
+
+        let segment = path.segments.last()?;
+
+        if segment.ident != "ControlFlow" {
+            return None;
+        }
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
     pub fn returns(&self) -> Type {
         match &self.output {
             ReturnType::Type(_, ty) => ty.as_ref().clone(),
@@ -148,12 +225,20 @@ impl HookFnSignature {
             }),
         }
     }
+
+    fn lifetimes_visitor(&self) -> Lifetimes {
+        match self.asyncness {
+            Some(_) => Lifetimes::unified("'life"),
+            None => Lifetimes::new("'life"),
+        }
+    }
 }
 
 impl Parse for HookFnSignature {
     fn parse(input: ParseStream) -> Result<Self> {
         let attrs = input.call(Attribute::parse_outer)?;
         let vis = input.parse::<Visibility>()?;
+        let asyncness: Option<Token![async]> = input.parse()?;
 
         let fn_token: Token![fn] = input.parse()?;
         let ident: Ident = input.parse()?;
@@ -205,6 +290,7 @@ impl Parse for HookFnSignature {
         Ok(Self {
             attrs,
             vis,
+            asyncness,
             fn_token,
             ident,
             paren_token,
@@ -14,8 +14,6 @@ pub fn hook(args: TokenStream, input: TokenStream) -> TokenStream {
 
     TokenStream::from(match args {
         Args::None => declaration::expand(krate, parse_macro_input!(input)),
-        Args::With(path, weight) => {
-            registration::expand(krate, path, weight, parse_macro_input!(input))
-        }
+        Args::With(args) => registration::expand(krate, *args, parse_macro_input!(input)),
     })
 }
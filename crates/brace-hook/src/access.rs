@@ -0,0 +1,157 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::HookInfo;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Access {
+    Allow,
+    Deny,
+    Neutral,
+}
+
+impl Access {
+    pub fn is_allowed(self) -> bool {
+        self == Access::Allow
+    }
+
+    pub fn is_denied(self) -> bool {
+        self == Access::Deny
+    }
+
+    pub fn is_neutral(self) -> bool {
+        self == Access::Neutral
+    }
+}
+
+impl From<bool> for Access {
+    fn from(allow: bool) -> Self {
+        if allow {
+            Access::Allow
+        } else {
+            Access::Deny
+        }
+    }
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Access::Allow => "allow",
+            Access::Deny => "deny",
+            Access::Neutral => "neutral",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessPolicy {
+    AnyDeny,
+    FirstDecisive,
+    Unanimous,
+    Majority,
+}
+
+impl AccessPolicy {
+    pub fn decide<I>(self, votes: I) -> Access
+    where
+        I: IntoIterator<Item = Access>,
+    {
+        let (mut allow, mut deny, mut neutral) = (0, 0, 0);
+
+        for vote in votes {
+            match vote {
+                Access::Allow => allow += 1,
+                Access::Deny => deny += 1,
+                Access::Neutral => neutral += 1,
+            }
+
+            if self == AccessPolicy::FirstDecisive && !vote.is_neutral() {
+                return vote;
+            }
+        }
+
+        match self {
+            AccessPolicy::AnyDeny if deny > 0 => Access::Deny,
+            AccessPolicy::AnyDeny if allow > 0 => Access::Allow,
+            // Neutral votes abstain, but a unanimous allow needs at least one
+            // voter to actually allow.
+            AccessPolicy::Unanimous if allow + deny + neutral == 0 => Access::Neutral,
+            AccessPolicy::Unanimous if allow > 0 && deny == 0 => Access::Allow,
+            AccessPolicy::Unanimous => Access::Deny,
+            AccessPolicy::Majority if allow > deny => Access::Allow,
+            AccessPolicy::Majority if deny > allow => Access::Deny,
+            _ => Access::Neutral,
+        }
+    }
+}
+
+impl fmt::Display for AccessPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AccessPolicy::AnyDeny => "any deny",
+            AccessPolicy::FirstDecisive => "first decisive",
+            AccessPolicy::Unanimous => "unanimous",
+            AccessPolicy::Majority => "majority",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccessDecision {
+    hook: &'static str,
+    policy: AccessPolicy,
+    access: Access,
+    votes: Vec<(Arc<HookInfo>, Access)>,
+}
+
+impl AccessDecision {
+    pub fn new(
+        hook: &'static str,
+        policy: AccessPolicy,
+        votes: Vec<(Arc<HookInfo>, Access)>,
+    ) -> Self {
+        Self {
+            hook,
+            policy,
+            access: policy.decide(votes.iter().map(|(_, vote)| *vote)),
+            votes,
+        }
+    }
+
+    pub fn hook(&self) -> &'static str {
+        self.hook
+    }
+
+    pub fn policy(&self) -> AccessPolicy {
+        self.policy
+    }
+
+    pub fn access(&self) -> Access {
+        self.access
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        self.access.is_allowed()
+    }
+
+    pub fn votes(&self) -> &[(Arc<HookInfo>, Access)] {
+        &self.votes
+    }
+}
+
+impl fmt::Display for AccessDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hook `{}` decided {} ({})",
+            self.hook, self.access, self.policy
+        )?;
+
+        for (info, vote) in &self.votes {
+            write!(f, "\n  {} voted {}", info.path(), vote)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Once, PoisonError};
+
+static CACHES: Mutex<Vec<&'static dyn Invalidate>> = Mutex::new(Vec::new());
+
+pub struct Cache<K, V> {
+    values: Mutex<Option<HashMap<K, Arc<V>>>>,
+    generation: AtomicU64,
+    registered: Once,
+}
+
+impl<K, V> Cache<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            values: Mutex::new(None),
+            generation: AtomicU64::new(0),
+            registered: Once::new(),
+        }
+    }
+
+    pub fn invalidate(&self) {
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(values) = &mut *values {
+            values.clear();
+        }
+    }
+}
+
+impl<K, V> Cache<K, V>
+where
+    K: Eq + Hash + Send + 'static,
+    V: Send + Sync + 'static,
+{
+    pub fn get_or_insert_with<F>(&'static self, key: K, f: F) -> Arc<V>
+    where
+        F: FnOnce() -> V,
+    {
+        let generation = match self.get(&key) {
+            Ok(value) => return value,
+            Err(generation) => generation,
+        };
+
+        // Registered before any value is stored and without holding the values
+        // lock, which `invalidate_all` takes while walking the caches.
+        self.registered.call_once(|| {
+            CACHES
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(self);
+        });
+
+        // Computed without holding the lock so that implementations may use
+        // other cached hooks. A value computed across an invalidation may be
+        // stale, so it is returned but not kept.
+        let value = Arc::new(f());
+        let mut values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if self.generation.load(Ordering::Relaxed) != generation {
+            return value;
+        }
+
+        values
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_insert(value)
+            .clone()
+    }
+
+    fn get(&self, key: &K) -> Result<Arc<V>, u64> {
+        let values = self.values.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match values.as_ref().and_then(|values| values.get(key)) {
+            Some(value) => Ok(value.clone()),
+            None => Err(self.generation.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<K, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait Invalidate: Sync {
+    fn invalidate(&self);
+}
+
+impl<K, V> Invalidate for Cache<K, V>
+where
+    K: Send,
+    V: Send + Sync,
+{
+    fn invalidate(&self) {
+        Cache::invalidate(self);
+    }
+}
+
+pub fn invalidate_all() {
+    let caches = CACHES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+
+    for cache in caches {
+        cache.invalidate();
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use crate::HookInfo;
+
+type Implementations = fn() -> Box<dyn Iterator<Item = Arc<HookInfo>>>;
+
+type Registered = fn() -> Box<dyn Iterator<Item = (Arc<HookInfo>, bool)>>;
+
+pub struct HookDeclaration {
+    name: &'static str,
+    module_path: &'static str,
+    signature: &'static str,
+    implementations: Implementations,
+    registered: Registered,
+}
+
+impl HookDeclaration {
+    pub fn new(
+        name: &'static str,
+        module_path: &'static str,
+        signature: &'static str,
+        implementations: Implementations,
+        registered: Registered,
+    ) -> Self {
+        Self {
+            name,
+            module_path,
+            signature,
+            implementations,
+            registered,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    pub fn signature(&self) -> &'static str {
+        self.signature
+    }
+
+    pub fn implementations(&self) -> impl Iterator<Item = Arc<HookInfo>> {
+        (self.implementations)()
+    }
+
+    pub fn registered(&self) -> impl Iterator<Item = (Arc<HookInfo>, bool)> {
+        (self.registered)()
+    }
+}
+
+impl fmt::Debug for HookDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HookDeclaration")
+            .field("name", &self.name)
+            .field("module_path", &self.module_path)
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
+impl fmt::Display for HookDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}: {}", self.module_path, self.name, self.signature)
+    }
+}
+
+inventory::collect!(HookDeclaration);
+
+pub fn catalog() -> impl Iterator<Item = &'static HookDeclaration> {
+    static CATALOG: OnceLock<Vec<&'static HookDeclaration>> = OnceLock::new();
+
+    CATALOG
+        .get_or_init(|| {
+            let mut hooks: Vec<_> = inventory::iter::<HookDeclaration>.into_iter().collect();
+
+            hooks.sort_by_key(|hook| (hook.module_path, hook.name));
+            hooks
+        })
+        .iter()
+        .copied()
+}
@@ -0,0 +1,45 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Mutex, PoisonError};
+
+use crate::catalog;
+
+static DIAGNOSTICS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+pub fn diagnostics() -> Vec<String> {
+    DIAGNOSTICS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+// Problems such as a `replaces` target that is not registered are only found
+// when a hook is resolved, so applications should call this once at startup
+// to resolve every declared hook up front. Hooks that fail to resolve, such as
+// those with cyclic ordering constraints, are reported instead of panicking.
+pub fn check() -> Vec<String> {
+    for hook in catalog() {
+        let res = panic::catch_unwind(AssertUnwindSafe(|| hook.registered().for_each(drop)));
+
+        if let Err(payload) = res {
+            let message = match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(payload) => match payload.downcast::<&'static str>() {
+                    Ok(message) => String::from(*message),
+                    Err(_) => format!("hook `{}` failed to resolve", hook.name()),
+                },
+            };
+
+            report(message);
+        }
+    }
+
+    diagnostics()
+}
+
+pub(crate) fn report(message: String) {
+    let mut diagnostics = DIAGNOSTICS.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if !diagnostics.contains(&message) {
+        diagnostics.push(message);
+    }
+}
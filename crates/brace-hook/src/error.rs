@@ -0,0 +1,106 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::HookInfo;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookError<E> {
+    hook: &'static str,
+    info: Arc<HookInfo>,
+    error: E,
+}
+
+impl<E> HookError<E> {
+    pub fn new(hook: &'static str, info: Arc<HookInfo>, error: E) -> Self {
+        Self { hook, info, error }
+    }
+
+    pub fn hook(&self) -> &'static str {
+        self.hook
+    }
+
+    pub fn info(&self) -> &HookInfo {
+        &self.info
+    }
+
+    pub fn error(&self) -> &E {
+        &self.error
+    }
+
+    pub fn into_error(self) -> E {
+        self.error
+    }
+}
+
+impl<E> fmt::Display for HookError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "implementation `{}` of hook `{}` failed",
+            self.info.path(),
+            self.hook
+        )
+    }
+}
+
+impl<E> Error for HookError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookPanic {
+    hook: &'static str,
+    info: Arc<HookInfo>,
+    message: String,
+}
+
+impl HookPanic {
+    pub fn new(hook: &'static str, info: Arc<HookInfo>, payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => String::from(*message),
+                Err(_) => String::from("Box<dyn Any>"),
+            },
+        };
+
+        Self {
+            hook,
+            info,
+            message,
+        }
+    }
+
+    pub fn hook(&self) -> &'static str {
+        self.hook
+    }
+
+    pub fn info(&self) -> &HookInfo {
+        &self.info
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for HookPanic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "implementation `{}` of hook `{}` panicked: {}",
+            self.info.path(),
+            self.hook,
+            self.message
+        )
+    }
+}
+
+impl Error for HookPanic {}
@@ -1,4 +1,18 @@
+use std::future::Future;
 use std::iter::FromIterator;
+use std::ops::ControlFlow;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+#[cfg(feature = "rayon")]
+use crate::ParallelHookIterator;
+use crate::{
+    Access, AccessDecision, AccessPolicy, Conflict, HookError, HookInfo, HookIterator, HookPanic,
+    JoinAll, Merge,
+};
 
 pub fn invoke<I>(iter: I) -> Vec<I::Item>
 where
@@ -21,6 +35,8 @@ where
     iter.for_each(drop);
 }
 
+// Keeps its original explicit early return.
+#[allow(clippy::question_mark)]
 pub fn try_exec<I, T, E>(iter: I) -> Result<(), E>
 where
     I: Iterator<Item = Result<T, E>>,
@@ -34,11 +50,333 @@ where
     Ok(())
 }
 
+pub fn try_invoke_all<I, T, E>(iter: I) -> Result<Vec<T>, Vec<E>>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    let (res, errors) = partition(iter);
+
+    if errors.is_empty() {
+        Ok(res)
+    } else {
+        Err(errors)
+    }
+}
+
+pub fn try_exec_all<I, T, E>(iter: I) -> Result<(), Vec<E>>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    try_invoke_all(iter).map(drop)
+}
+
+pub fn partition<I, T, E>(iter: I) -> (Vec<T>, Vec<E>)
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    let mut res = Vec::with_capacity(iter.size_hint().0);
+    let mut errors = Vec::new();
+
+    for item in iter {
+        match item {
+            Ok(item) => res.push(item),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    (res, errors)
+}
+
+pub fn tag_errors<I, T, E>(iter: I) -> impl Iterator<Item = Result<T, (Arc<HookInfo>, E)>>
+where
+    I: HookIterator<Item = Result<T, E>>,
+{
+    iter.tagged()
+        .map(|(info, res)| res.map_err(|err| (info, err)))
+}
+
+pub fn try_invoke_tagged<I, T, E>(iter: I) -> Result<Vec<T>, HookError<E>>
+where
+    I: HookIterator<Item = Result<T, E>>,
+{
+    Result::from_iter(hook_errors(iter))
+}
+
+pub fn try_exec_tagged<I, T, E>(iter: I) -> Result<(), HookError<E>>
+where
+    I: HookIterator<Item = Result<T, E>>,
+{
+    try_exec(hook_errors(iter))
+}
+
+pub fn try_invoke_all_tagged<I, T, E>(iter: I) -> Result<Vec<T>, Vec<HookError<E>>>
+where
+    I: HookIterator<Item = Result<T, E>>,
+{
+    try_invoke_all(hook_errors(iter))
+}
+
+fn hook_errors<I, T, E>(iter: I) -> impl Iterator<Item = Result<T, HookError<E>>>
+where
+    I: HookIterator<Item = Result<T, E>>,
+{
+    let hook = iter.hook();
+
+    iter.tagged()
+        .map(move |(info, res)| res.map_err(|err| HookError::new(hook, info, err)))
+}
+
+pub fn invoke_isolated<I>(mut iter: I) -> (Vec<I::Item>, Vec<HookPanic>)
+where
+    I: HookIterator,
+{
+    let hook = iter.hook();
+    let mut res = Vec::with_capacity(iter.size_hint().0);
+    let mut panics = Vec::new();
+
+    while let Some(info) = iter.peek_info().cloned() {
+        match panic::catch_unwind(AssertUnwindSafe(|| iter.next())) {
+            Ok(Some(item)) => res.push(item),
+            Ok(None) => break,
+            Err(payload) => panics.push(HookPanic::new(hook, info, payload)),
+        }
+    }
+
+    (res, panics)
+}
+
+pub fn exec_isolated<I>(iter: I) -> Vec<HookPanic>
+where
+    I: HookIterator,
+{
+    invoke_isolated(iter).1
+}
+
+pub fn first_some<I, T>(iter: I) -> Option<T>
+where
+    I: Iterator<Item = Option<T>>,
+{
+    find(iter, |res| res)
+}
+
+pub fn last_some<I, T>(iter: I) -> Option<T>
+where
+    I: DoubleEndedIterator<Item = Option<T>>,
+{
+    find(iter.rev(), |res| res)
+}
+
+pub fn find<I, F, T>(mut iter: I, f: F) -> Option<T>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> Option<T>,
+{
+    iter.find_map(f)
+}
+
+pub fn access<I>(iter: I, policy: AccessPolicy) -> AccessDecision
+where
+    I: HookIterator<Item = Access>,
+{
+    let hook = iter.hook();
+    let mut votes = Vec::new();
+
+    for (info, vote) in iter.tagged() {
+        votes.push((info, vote));
+
+        if policy == AccessPolicy::FirstDecisive && !vote.is_neutral() {
+            break;
+        }
+    }
+
+    AccessDecision::new(hook, policy, votes)
+}
+
+pub fn merge<I, T>(iter: I, conflict: Conflict) -> Result<T, HookError<T::Key>>
+where
+    I: HookIterator<Item = T>,
+    T: Merge + Default,
+{
+    let hook = iter.hook();
+    let mut res = T::default();
+
+    for (info, item) in iter.tagged() {
+        res.merge_from(item, conflict)
+            .map_err(|key| HookError::new(hook, info, key))?;
+    }
+
+    Ok(res)
+}
+
+pub fn fold<I, B, F>(iter: I, init: B, f: F) -> B
+where
+    I: Iterator,
+    F: FnMut(B, I::Item) -> B,
+{
+    iter.fold(init, f)
+}
+
+pub fn until_break<I, B, C>(iter: I) -> ControlFlow<B, Vec<C>>
+where
+    I: Iterator<Item = ControlFlow<B, C>>,
+{
+    let mut res = Vec::with_capacity(iter.size_hint().0);
+
+    for flow in iter {
+        match flow {
+            ControlFlow::Continue(value) => res.push(value),
+            ControlFlow::Break(value) => return ControlFlow::Break(value),
+        }
+    }
+
+    ControlFlow::Continue(res)
+}
+
+pub fn dispatch<I, B, C>(iter: I) -> Option<(Arc<HookInfo>, B)>
+where
+    I: HookIterator<Item = ControlFlow<B, C>>,
+{
+    for (info, flow) in iter.tagged() {
+        if let ControlFlow::Break(value) = flow {
+            return Some((info, value));
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "rayon")]
+pub fn par_invoke<I>(iter: I) -> Vec<I::Item>
+where
+    I: ParallelHookIterator + ExactSizeIterator + Sync,
+    I::Item: Send,
+{
+    (0..iter.len())
+        .into_par_iter()
+        .map(|pos| iter.call(pos))
+        .collect()
+}
+
+#[cfg(feature = "rayon")]
+pub fn par_exec<I>(iter: I)
+where
+    I: ParallelHookIterator + ExactSizeIterator + Sync,
+    I::Item: Send,
+{
+    (0..iter.len())
+        .into_par_iter()
+        .for_each(|pos| drop(iter.call(pos)));
+}
+
+pub async fn invoke_async<I>(iter: I) -> Vec<<I::Item as Future>::Output>
+where
+    I: Iterator,
+    I::Item: Future,
+{
+    let mut res = Vec::with_capacity(iter.size_hint().0);
+
+    for fut in iter {
+        res.push(fut.await);
+    }
+
+    res
+}
+
+pub async fn try_invoke_async<I, T, E>(iter: I) -> Result<Vec<T>, E>
+where
+    I: Iterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    let mut res = Vec::with_capacity(iter.size_hint().0);
+
+    for fut in iter {
+        res.push(fut.await?);
+    }
+
+    Ok(res)
+}
+
+pub async fn exec_async<I>(iter: I)
+where
+    I: Iterator,
+    I::Item: Future,
+{
+    for fut in iter {
+        fut.await;
+    }
+}
+
+pub async fn try_exec_async<I, T, E>(iter: I) -> Result<(), E>
+where
+    I: Iterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    for fut in iter {
+        fut.await?;
+    }
+
+    Ok(())
+}
+
+pub fn join<I>(iter: I) -> JoinAll<I::Item>
+where
+    I: Iterator,
+    I::Item: Future,
+{
+    JoinAll::new(iter)
+}
+
+pub async fn try_join<I, T, E>(iter: I) -> Result<Vec<T>, E>
+where
+    I: Iterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    Result::from_iter(join(iter).await)
+}
+
 #[cfg(test)]
 mod tests {
     extern crate self as brace_hook;
 
-    use brace_hook::hook;
+    use std::cell::RefCell;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+    use std::error::Error;
+    use std::future::Future;
+    use std::num::ParseIntError;
+    use std::ops::ControlFlow;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Context, Poll, Waker};
+
+    use brace_hook::{hook, Access, AccessPolicy, Conflict, Merge};
+
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = Box::pin(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        loop {
+            if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                return res;
+            }
+        }
+    }
+
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+
+            self.0 = true;
+            cx.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
 
     #[hook]
     fn my_hook(input: &str) -> Result<String, String> {}
@@ -75,6 +413,39 @@ mod tests {
         assert_eq!(res[1], String::from("my_hook_2: hello"));
     }
 
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_invoke() {
+        let res = hook::par_invoke(my_hook::with("hello"));
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0], Ok(String::from("my_hook_1: hello")));
+        assert_eq!(res[1], Ok(String::from("my_hook_2: hello")));
+    }
+
+    #[hook]
+    fn my_count_hook(count: &AtomicUsize) {}
+
+    #[hook(my_count_hook, 1)]
+    fn my_count_hook_1(count: &AtomicUsize) {
+        count.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[hook(my_count_hook, 2)]
+    fn my_count_hook_2(count: &AtomicUsize) {
+        count.fetch_add(2, Ordering::SeqCst);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_exec() {
+        let count = AtomicUsize::new(0);
+
+        hook::par_exec(my_count_hook::with(&count));
+
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
     #[hook]
     fn my_bad_hook(input: &str) -> Result<String, String> {}
 
@@ -96,6 +467,121 @@ mod tests {
         assert_eq!(res, Err(String::from("my_bad_hook_1: hello")));
     }
 
+    #[test]
+    fn test_try_invoke_all() {
+        assert_eq!(
+            hook::try_invoke_all(my_hook::with("hello")),
+            Ok(vec![
+                String::from("my_hook_1: hello"),
+                String::from("my_hook_2: hello"),
+            ])
+        );
+        assert_eq!(
+            hook::try_invoke_all(my_multi_bad_hook::with("hello")),
+            Err(vec![
+                String::from("my_multi_bad_hook_1: hello"),
+                String::from("my_multi_bad_hook_3: hello"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_partition() {
+        let (res, errors) = hook::partition(my_multi_bad_hook::with("hello"));
+
+        assert_eq!(res, vec![String::from("my_multi_bad_hook_2: hello")]);
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_tag_errors() {
+        let errors = hook::try_exec_all(hook::tag_errors(my_multi_bad_hook::with("hello")))
+            .unwrap_err()
+            .into_iter()
+            .map(|(info, err)| (info.name(), err))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    "my_multi_bad_hook_1",
+                    String::from("my_multi_bad_hook_1: hello")
+                ),
+                (
+                    "my_multi_bad_hook_3",
+                    String::from("my_multi_bad_hook_3: hello")
+                ),
+            ]
+        );
+    }
+
+    #[hook]
+    fn my_multi_bad_hook(input: &str) -> Result<String, String> {}
+
+    #[hook(my_multi_bad_hook, 1)]
+    fn my_multi_bad_hook_1(input: &str) -> Result<String, String> {
+        Err(format!("my_multi_bad_hook_1: {}", input))
+    }
+
+    #[hook(my_multi_bad_hook, 2)]
+    fn my_multi_bad_hook_2(input: &str) -> Result<String, String> {
+        Ok(format!("my_multi_bad_hook_2: {}", input))
+    }
+
+    #[hook(my_multi_bad_hook, 3)]
+    fn my_multi_bad_hook_3(input: &str) -> Result<String, String> {
+        Err(format!("my_multi_bad_hook_3: {}", input))
+    }
+
+    #[hook]
+    fn my_parse_hook(input: &str) -> Result<i32, ParseIntError> {}
+
+    #[hook(my_parse_hook, 1)]
+    fn my_parse_hook_1(input: &str) -> Result<i32, ParseIntError> {
+        input.parse()
+    }
+
+    #[hook(my_parse_hook, 2)]
+    fn my_parse_hook_2(input: &str) -> Result<i32, ParseIntError> {
+        format!("{}x", input).parse()
+    }
+
+    #[test]
+    fn test_try_invoke_tagged() {
+        let err = hook::try_invoke_tagged(my_parse_hook::with("1")).unwrap_err();
+
+        assert_eq!(err.hook(), "my_parse_hook");
+        assert_eq!(err.info().name(), "my_parse_hook_2");
+        assert_eq!(err.info().weight(), 2);
+        assert_eq!(
+            err.to_string(),
+            "implementation `brace_hook::hook::tests::my_parse_hook_2` of hook `my_parse_hook` failed"
+        );
+        assert!(err.source().is_some());
+
+        let res = hook::try_invoke_tagged(my_hook::with("hello"));
+
+        assert_eq!(res.map(|res| res.len()), Ok(2));
+    }
+
+    #[test]
+    fn test_try_exec_tagged() {
+        let err = hook::try_exec_tagged(my_bad_hook::with("hello")).unwrap_err();
+
+        assert_eq!(err.info().name(), "my_bad_hook_1");
+        assert_eq!(err.into_error(), "my_bad_hook_1: hello");
+    }
+
+    #[test]
+    fn test_try_invoke_all_tagged() {
+        let errors = hook::try_invoke_all_tagged(my_multi_bad_hook::with("hello")).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].info().name(), "my_multi_bad_hook_1");
+        assert_eq!(errors[1].info().name(), "my_multi_bad_hook_3");
+    }
+
     #[hook]
     fn my_mut_hook(output: &mut Vec<&str>) -> Result<(), String> {}
 
@@ -160,4 +646,451 @@ mod tests {
         assert_eq!(items.len(), 1);
         assert_eq!(items[0], "my_bad_mut_hook_1");
     }
+
+    #[hook]
+    fn my_panic_hook(log: &mut Vec<&str>) -> &'static str {}
+
+    #[hook(my_panic_hook, 1)]
+    fn my_panic_hook_1(log: &mut Vec<&str>) -> &'static str {
+        log.push("my_panic_hook_1");
+        "my_panic_hook_1"
+    }
+
+    #[hook(my_panic_hook, 2)]
+    fn my_panic_hook_2(log: &mut Vec<&str>) -> &'static str {
+        log.push("my_panic_hook_2");
+        panic!("my_panic_hook_2: {}", log.len())
+    }
+
+    #[hook(my_panic_hook, 3)]
+    fn my_panic_hook_3(log: &mut Vec<&str>) -> &'static str {
+        log.push("my_panic_hook_3");
+        "my_panic_hook_3"
+    }
+
+    #[test]
+    fn test_invoke_isolated() {
+        let mut log = Vec::new();
+        let (res, panics) = hook::invoke_isolated(my_panic_hook::with(&mut log));
+
+        assert_eq!(res, vec!["my_panic_hook_1", "my_panic_hook_3"]);
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].hook(), "my_panic_hook");
+        assert_eq!(panics[0].info().name(), "my_panic_hook_2");
+        assert_eq!(panics[0].message(), "my_panic_hook_2: 2");
+        assert_eq!(log.len(), 3);
+    }
+
+    #[test]
+    fn test_exec_isolated() {
+        let mut log = Vec::new();
+
+        assert_eq!(hook::exec_isolated(my_mut_hook::with(&mut log)), vec![]);
+
+        let mut log = Vec::new();
+        let panics = hook::exec_isolated(my_panic_hook::with(&mut log));
+
+        assert_eq!(
+            panics[0].to_string(),
+            "implementation `brace_hook::hook::tests::my_panic_hook_2` of hook `my_panic_hook` \
+             panicked: my_panic_hook_2: 2"
+        );
+    }
+
+    #[hook]
+    fn my_option_hook(log: &mut Vec<&str>, input: &str) -> Option<String> {}
+
+    #[hook(my_option_hook, 1)]
+    fn my_option_hook_1(log: &mut Vec<&str>, _: &str) -> Option<String> {
+        log.push("my_option_hook_1");
+        None
+    }
+
+    #[hook(my_option_hook, 2)]
+    fn my_option_hook_2(log: &mut Vec<&str>, input: &str) -> Option<String> {
+        log.push("my_option_hook_2");
+        Some(format!("my_option_hook_2: {}", input))
+    }
+
+    #[hook(my_option_hook, 3)]
+    fn my_option_hook_3(log: &mut Vec<&str>, input: &str) -> Option<String> {
+        log.push("my_option_hook_3");
+        Some(format!("my_option_hook_3: {}", input))
+    }
+
+    #[hook(my_option_hook, 4)]
+    fn my_option_hook_4(log: &mut Vec<&str>, _: &str) -> Option<String> {
+        log.push("my_option_hook_4");
+        None
+    }
+
+    #[test]
+    fn test_first_some() {
+        let mut log = Vec::new();
+        let res = hook::first_some(my_option_hook::with(&mut log, "hello"));
+
+        assert_eq!(res, Some(String::from("my_option_hook_2: hello")));
+        assert_eq!(log, vec!["my_option_hook_1", "my_option_hook_2"]);
+    }
+
+    #[test]
+    fn test_last_some() {
+        let mut log = Vec::new();
+        let res = hook::last_some(my_option_hook::with(&mut log, "hello"));
+
+        assert_eq!(res, Some(String::from("my_option_hook_3: hello")));
+        assert_eq!(log, vec!["my_option_hook_4", "my_option_hook_3"]);
+    }
+
+    #[test]
+    fn test_find() {
+        let mut log = Vec::new();
+        let res = hook::find(my_option_hook::with(&mut log, "hello"), |res| {
+            res.filter(|res| res.starts_with("my_option_hook_3"))
+        });
+
+        assert_eq!(res, Some(String::from("my_option_hook_3: hello")));
+        assert_eq!(log.len(), 3);
+    }
+
+    #[hook]
+    fn my_access_hook(op: &str) -> Access {}
+
+    #[hook(my_access_hook, 1)]
+    fn my_access_hook_1(_: &str) -> Access {
+        Access::Neutral
+    }
+
+    #[hook(my_access_hook, 2)]
+    fn my_access_hook_2(op: &str) -> Access {
+        match op {
+            "view" => Access::Allow,
+            _ => Access::Neutral,
+        }
+    }
+
+    #[hook(my_access_hook, 3)]
+    fn my_access_hook_3(op: &str) -> Access {
+        Access::from(op != "delete")
+    }
+
+    #[hook(my_access_hook, 4)]
+    fn my_access_hook_4(op: &str) -> Access {
+        match op {
+            "view" => Access::Deny,
+            _ => Access::Neutral,
+        }
+    }
+
+    #[test]
+    fn test_access() {
+        let decide = |op, policy| hook::access(my_access_hook::with(op), policy).access();
+
+        assert_eq!(decide("view", AccessPolicy::AnyDeny), Access::Deny);
+        assert_eq!(decide("view", AccessPolicy::FirstDecisive), Access::Allow);
+        assert_eq!(decide("view", AccessPolicy::Unanimous), Access::Deny);
+        assert_eq!(decide("view", AccessPolicy::Majority), Access::Allow);
+
+        assert_eq!(decide("edit", AccessPolicy::AnyDeny), Access::Allow);
+        assert_eq!(decide("edit", AccessPolicy::FirstDecisive), Access::Allow);
+        assert_eq!(decide("edit", AccessPolicy::Unanimous), Access::Allow);
+        assert_eq!(decide("edit", AccessPolicy::Majority), Access::Allow);
+
+        assert_eq!(decide("delete", AccessPolicy::AnyDeny), Access::Deny);
+        assert_eq!(decide("delete", AccessPolicy::Majority), Access::Deny);
+    }
+
+    #[test]
+    fn test_access_explanation() {
+        let res = hook::access(my_access_hook::with("view"), AccessPolicy::FirstDecisive);
+
+        assert!(res.is_allowed());
+        assert_eq!(res.votes().len(), 2);
+        assert_eq!(
+            res.to_string(),
+            "hook `my_access_hook` decided allow (first decisive)\n  \
+             brace_hook::hook::tests::my_access_hook_1 voted neutral\n  \
+             brace_hook::hook::tests::my_access_hook_2 voted allow"
+        );
+    }
+
+    #[test]
+    fn test_access_without_votes() {
+        let res = AccessPolicy::Unanimous.decide(Vec::new());
+
+        assert_eq!(res, Access::Neutral);
+
+        let votes = vec![Access::Neutral, Access::Neutral];
+
+        assert_eq!(AccessPolicy::AnyDeny.decide(votes.clone()), Access::Neutral);
+        assert_eq!(AccessPolicy::Unanimous.decide(votes), Access::Deny);
+    }
+
+    #[hook]
+    fn my_routes_hook() -> HashMap<&'static str, &'static str> {}
+
+    #[hook(my_routes_hook, 1)]
+    fn my_routes_hook_1() -> HashMap<&'static str, &'static str> {
+        HashMap::from([("/", "home"), ("/about", "about")])
+    }
+
+    #[hook(my_routes_hook, 2)]
+    fn my_routes_hook_2() -> HashMap<&'static str, &'static str> {
+        HashMap::from([("/", "landing"), ("/blog", "blog")])
+    }
+
+    #[test]
+    fn test_merge() {
+        let res = hook::merge(my_routes_hook::with(), Conflict::FirstWins).unwrap();
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res["/"], "home");
+
+        let res = hook::merge(my_routes_hook::with(), Conflict::LastWins).unwrap();
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res["/"], "landing");
+
+        let err = hook::merge(my_routes_hook::with(), Conflict::Error).unwrap_err();
+
+        assert_eq!(err.info().name(), "my_routes_hook_2");
+        assert_eq!(err.into_error(), "/");
+    }
+
+    #[test]
+    fn test_merge_collections() {
+        let mut items = vec![1, 2];
+
+        items.merge_from(vec![2, 3], Conflict::Error).unwrap();
+
+        assert_eq!(items, vec![1, 2, 2, 3]);
+
+        let mut set = HashSet::from([1, 2]);
+
+        assert_eq!(
+            set.merge_from(HashSet::from([2, 3]), Conflict::Error),
+            Err(2)
+        );
+
+        let mut map = BTreeMap::from([(1, "a")]);
+
+        map.merge_from(BTreeMap::from([(1, "b"), (2, "c")]), Conflict::LastWins)
+            .unwrap();
+
+        assert_eq!(map, BTreeMap::from([(1, "b"), (2, "c")]));
+
+        let mut value = None;
+
+        value.merge_from(Some(1), Conflict::Error).unwrap();
+        value.merge_from(None, Conflict::Error).unwrap();
+
+        assert_eq!(value.merge_from(Some(2), Conflict::FirstWins), Ok(()));
+        assert_eq!(value, Some(1));
+        assert_eq!(value.merge_from(Some(3), Conflict::Error), Err(3));
+    }
+
+    #[test]
+    fn test_fold() {
+        let res = hook::fold(my_hook::with("hello"), Vec::new(), |mut acc, res| {
+            acc.push(res.unwrap());
+            acc
+        });
+
+        assert_eq!(res, vec!["my_hook_1: hello", "my_hook_2: hello"]);
+    }
+
+    #[hook]
+    fn my_event_hook(log: &mut Vec<&str>, input: &str) -> ControlFlow<String> {}
+
+    #[hook(my_event_hook, 1)]
+    fn my_event_hook_1(log: &mut Vec<&str>, _: &str) -> ControlFlow<String> {
+        log.push("my_event_hook_1");
+        ControlFlow::Continue(())
+    }
+
+    #[hook(my_event_hook, 2)]
+    fn my_event_hook_2(log: &mut Vec<&str>, input: &str) -> ControlFlow<String> {
+        log.push("my_event_hook_2");
+
+        match input {
+            "stop" => ControlFlow::Break(format!("my_event_hook_2: {}", input)),
+            _ => ControlFlow::Continue(()),
+        }
+    }
+
+    #[hook(my_event_hook, 3)]
+    fn my_event_hook_3(log: &mut Vec<&str>, _: &str) -> ControlFlow<String> {
+        log.push("my_event_hook_3");
+        ControlFlow::Continue(())
+    }
+
+    #[test]
+    fn test_until_break() {
+        let mut log = Vec::new();
+        let res = hook::until_break(my_event_hook::with(&mut log, "stop"));
+
+        assert_eq!(
+            res,
+            ControlFlow::Break(String::from("my_event_hook_2: stop"))
+        );
+        assert_eq!(log, vec!["my_event_hook_1", "my_event_hook_2"]);
+
+        let mut log = Vec::new();
+        let res = hook::until_break(my_event_hook::with(&mut log, "go"));
+
+        assert_eq!(res, ControlFlow::Continue(vec![(), (), ()]));
+        assert_eq!(log.len(), 3);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut log = Vec::new();
+        let (info, res) = hook::dispatch(my_event_hook::with(&mut log, "stop")).unwrap();
+
+        assert_eq!(info.name(), "my_event_hook_2");
+        assert_eq!(res, "my_event_hook_2: stop");
+        assert_eq!(log, vec!["my_event_hook_1", "my_event_hook_2"]);
+
+        let mut log = Vec::new();
+        let res = my_event_hook::dispatch(&mut log, "go");
+
+        assert!(res.is_none());
+        assert_eq!(log.len(), 3);
+    }
+
+    #[hook]
+    #[hook_attr(local)]
+    async fn my_async_hook(log: &RefCell<Vec<String>>, input: &str) -> Result<String, String> {}
+
+    #[hook(my_async_hook, 1)]
+    async fn my_async_hook_1(log: &RefCell<Vec<String>>, input: &str) -> Result<String, String> {
+        log.borrow_mut()
+            .push(String::from("my_async_hook_1: start"));
+        YieldNow(false).await;
+        log.borrow_mut().push(String::from("my_async_hook_1: end"));
+        Ok(format!("my_async_hook_1: {}", input))
+    }
+
+    #[hook(my_async_hook, 2)]
+    async fn my_async_hook_2(log: &RefCell<Vec<String>>, input: &str) -> Result<String, String> {
+        log.borrow_mut()
+            .push(String::from("my_async_hook_2: start"));
+        YieldNow(false).await;
+        log.borrow_mut().push(String::from("my_async_hook_2: end"));
+        Ok(format!("my_async_hook_2: {}", input))
+    }
+
+    #[test]
+    fn test_invoke_async() {
+        let log = RefCell::new(Vec::new());
+        let res = block_on(hook::invoke_async(my_async_hook::with(&log, "hello")));
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0], Ok(String::from("my_async_hook_1: hello")));
+        assert_eq!(res[1], Ok(String::from("my_async_hook_2: hello")));
+        assert_eq!(
+            log.into_inner(),
+            vec![
+                "my_async_hook_1: start",
+                "my_async_hook_1: end",
+                "my_async_hook_2: start",
+                "my_async_hook_2: end",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_invoke_async_ok() {
+        let log = RefCell::new(Vec::new());
+        let res = block_on(hook::try_invoke_async(my_async_hook::with(&log, "hello")));
+
+        assert_eq!(
+            res,
+            Ok(vec![
+                String::from("my_async_hook_1: hello"),
+                String::from("my_async_hook_2: hello"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_join() {
+        let log = RefCell::new(Vec::new());
+        let res = block_on(hook::join(my_async_hook::with(&log, "hello")));
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0], Ok(String::from("my_async_hook_1: hello")));
+        assert_eq!(res[1], Ok(String::from("my_async_hook_2: hello")));
+        assert_eq!(
+            log.into_inner(),
+            vec![
+                "my_async_hook_1: start",
+                "my_async_hook_2: start",
+                "my_async_hook_1: end",
+                "my_async_hook_2: end",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`JoinAll` polled after completion")]
+    fn test_join_polled_after_completion() {
+        let mut fut = hook::join(my_bad_async_hook::with("hello"));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(Pin::new(&mut fut).poll(&mut cx).is_ready());
+
+        let _ = Pin::new(&mut fut).poll(&mut cx);
+    }
+
+    #[hook]
+    async fn my_bad_async_hook(input: &str) -> Result<String, String> {}
+
+    #[hook(my_bad_async_hook, 1)]
+    async fn my_bad_async_hook_1(input: &str) -> Result<String, String> {
+        Err(format!("my_bad_async_hook_1: {}", input))
+    }
+
+    #[hook(my_bad_async_hook, 2)]
+    async fn my_bad_async_hook_2(input: &str) -> Result<String, String> {
+        Ok(format!("my_bad_async_hook_2: {}", input))
+    }
+
+    #[test]
+    fn test_try_invoke_async_err() {
+        let res = block_on(hook::try_invoke_async(my_bad_async_hook::with("hello")));
+
+        assert_eq!(res, Err(String::from("my_bad_async_hook_1: hello")));
+    }
+
+    #[test]
+    fn test_exec_async() {
+        let log = RefCell::new(Vec::new());
+
+        block_on(hook::exec_async(my_async_hook::with(&log, "hello")));
+
+        assert_eq!(log.borrow().len(), 4);
+    }
+
+    #[test]
+    fn test_try_exec_async() {
+        let log = RefCell::new(Vec::new());
+
+        assert_eq!(
+            block_on(hook::try_exec_async(my_async_hook::with(&log, "hello"))),
+            Ok(())
+        );
+        assert_eq!(
+            block_on(hook::try_exec_async(my_bad_async_hook::with("hello"))),
+            Err(String::from("my_bad_async_hook_1: hello"))
+        );
+    }
+
+    #[test]
+    fn test_try_join() {
+        assert_eq!(
+            block_on(hook::try_join(my_bad_async_hook::with("hello"))),
+            Err(String::from("my_bad_async_hook_1: hello"))
+        );
+    }
 }
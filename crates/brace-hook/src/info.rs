@@ -0,0 +1,155 @@
+use std::fmt;
+
+use crate::Phase;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookInfo {
+    path: &'static str,
+    name: Option<&'static str>,
+    module_path: &'static str,
+    file: Option<&'static str>,
+    line: Option<u32>,
+    weight: i32,
+    default: bool,
+    tags: Vec<&'static str>,
+    after: Vec<&'static str>,
+    before: Vec<&'static str>,
+    replaces: Vec<&'static str>,
+    disables: Vec<&'static str>,
+}
+
+impl HookInfo {
+    pub fn new(path: &'static str, weight: i32, default: bool) -> Self {
+        Self {
+            path,
+            name: None,
+            module_path: path.rsplit_once("::").map_or("", |(module, _)| module),
+            file: None,
+            line: None,
+            weight,
+            default,
+            tags: Vec::new(),
+            after: Vec::new(),
+            before: Vec::new(),
+            replaces: Vec::new(),
+            disables: Vec::new(),
+        }
+    }
+
+    pub fn with_location(
+        mut self,
+        module_path: &'static str,
+        file: &'static str,
+        line: u32,
+    ) -> Self {
+        self.module_path = module_path;
+        self.file = Some(file);
+        self.line = Some(line);
+        self
+    }
+
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &'static str) -> Self {
+        self.tags.push(tag);
+        self
+    }
+
+    pub fn run_after(mut self, path: &'static str) -> Self {
+        self.after.push(path);
+        self
+    }
+
+    pub fn run_before(mut self, path: &'static str) -> Self {
+        self.before.push(path);
+        self
+    }
+
+    pub fn replace(mut self, path: &'static str) -> Self {
+        self.replaces.push(path);
+        self
+    }
+
+    pub fn disable(mut self, path: &'static str) -> Self {
+        self.disables.push(path);
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self.name {
+            Some(name) => name,
+            None => self.path.rsplit("::").next().unwrap_or(self.path),
+        }
+    }
+
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    pub fn file(&self) -> Option<&'static str> {
+        self.file
+    }
+
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    pub fn weight(&self) -> i32 {
+        self.weight
+    }
+
+    pub fn phase(&self) -> Phase {
+        Phase::of(self.weight)
+    }
+
+    pub fn phase_weight(&self) -> i32 {
+        self.weight - self.phase().offset()
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.default
+    }
+
+    pub fn tags(&self) -> &[&'static str] {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+
+    pub fn after(&self) -> &[&'static str] {
+        &self.after
+    }
+
+    pub fn before(&self) -> &[&'static str] {
+        &self.before
+    }
+
+    pub fn replaces(&self) -> &[&'static str] {
+        &self.replaces
+    }
+
+    pub fn disables(&self) -> &[&'static str] {
+        &self.disables
+    }
+}
+
+impl fmt::Display for HookInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (weight {})", self.path, self.weight)?;
+
+        if let (Some(file), Some(line)) = (self.file, self.line) {
+            write!(f, " at {}:{}", file, line)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use crate::HookInfo;
+
+pub trait HookIterator: Iterator {
+    fn hook(&self) -> &'static str;
+
+    fn peek_info(&self) -> Option<&Arc<HookInfo>>;
+
+    fn next_tagged(&mut self) -> Option<(Arc<HookInfo>, Self::Item)>;
+
+    fn tagged(self) -> Tagged<Self>
+    where
+        Self: Sized,
+    {
+        Tagged { iter: self }
+    }
+}
+
+pub struct Tagged<I> {
+    iter: I,
+}
+
+impl<I> Iterator for Tagged<I>
+where
+    I: HookIterator,
+{
+    type Item = (Arc<HookInfo>, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_tagged()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub trait ParallelHookIterator: HookIterator {
+    fn call(&self, pos: usize) -> Self::Item;
+}
@@ -0,0 +1,76 @@
+use std::future::Future;
+use std::iter::FromIterator;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+pub struct JoinAll<F: Future> {
+    futures: Vec<Option<Pin<Box<F>>>>,
+    outputs: Vec<Option<F::Output>>,
+    done: bool,
+}
+
+impl<F: Future> JoinAll<F> {
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+    {
+        let futures: Vec<_> = iter.into_iter().map(|fut| Some(Box::pin(fut))).collect();
+        let outputs = futures.iter().map(|_| None).collect();
+
+        Self {
+            futures,
+            outputs,
+            done: false,
+        }
+    }
+}
+
+impl<F: Future> FromIterator<F> for JoinAll<F> {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = F>,
+    {
+        Self::new(iter)
+    }
+}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut pending = false;
+
+        if this.done {
+            panic!("`JoinAll` polled after completion");
+        }
+
+        for (slot, output) in this.futures.iter_mut().zip(this.outputs.iter_mut()) {
+            if let Some(fut) = slot {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(res) => {
+                        *output = Some(res);
+                        *slot = None;
+                    }
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+
+        if pending {
+            return Poll::Pending;
+        }
+
+        this.done = true;
+
+        Poll::Ready(
+            mem::take(&mut this.outputs)
+                .into_iter()
+                .map(|output| output.expect("every future has completed"))
+                .collect(),
+        )
+    }
+}
+
+impl<F: Future> Unpin for JoinAll<F> {}
@@ -1,8 +1,36 @@
 pub use brace_hook_macros::hook;
 pub use inventory;
+#[cfg(feature = "rayon")]
+pub use rayon;
 
+pub use self::access::{Access, AccessDecision, AccessPolicy};
+pub use self::cache::{invalidate_all, Cache};
+pub use self::catalog::{catalog, HookDeclaration};
+pub use self::diagnostics::{check, diagnostics};
+pub use self::error::{HookError, HookPanic};
 pub use self::hook::*;
+pub use self::info::HookInfo;
+pub use self::iter::{HookIterator, ParallelHookIterator, Tagged};
+pub use self::join::JoinAll;
+pub use self::merge::{Conflict, Merge};
+pub use self::next::Next;
+pub use self::phase::Phase;
+pub use self::registry::{Hooks, Implementation, RegistrationHandle, Registry};
+pub use self::scope::Scope;
 
+mod access;
+mod cache;
+mod catalog;
+mod diagnostics;
+mod error;
+mod info;
+mod iter;
+mod join;
 mod macros;
+mod merge;
+mod next;
+mod phase;
+mod registry;
+mod scope;
 
 pub mod hook;
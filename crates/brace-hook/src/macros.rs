@@ -1,23 +1,33 @@
 #[macro_export]
 macro_rules! register {
+    ([$($type:path),+ $(,)?], $hook:path) => {
+        $($crate::register!($type, $hook);)+
+    };
+
+    ([$($type:path),+ $(,)?], $hook:path, $weight:expr) => {
+        $($crate::register!($type, $hook, $weight);)+
+    };
+
+    ([$($type:path),+ $(,)?], $hook:path, $weight:expr, $default:expr) => {
+        $($crate::register!($type, $hook, $weight, $default);)+
+    };
+
     ($type:path, $hook:path) => {
-        $crate::inventory::submit! {
-            #![crate = $crate]
-            $type::new($hook, 0, false)
-        }
+        $crate::register!($type, $hook, 0, false);
     };
 
     ($type:path, $hook:path, $weight:expr) => {
-        $crate::inventory::submit! {
-            #![crate = $crate]
-            $type::new($hook, $weight, false)
-        }
+        $crate::register!($type, $hook, $weight, false);
     };
 
     ($type:path, $hook:path, $weight:expr, $default:expr) => {
         $crate::inventory::submit! {
             #![crate = $crate]
-            $type::new($hook, $weight, $default)
+            $type::with_info(
+                $hook,
+                $crate::HookInfo::new(std::any::type_name_of_val(&$hook), $weight, $default)
+                    .with_location(module_path!(), file!(), line!()),
+            )
         }
     };
 }
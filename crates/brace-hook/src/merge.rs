@@ -0,0 +1,119 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::Infallible;
+use std::hash::{BuildHasher, Hash};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Conflict {
+    FirstWins,
+    LastWins,
+    Error,
+}
+
+pub trait Merge {
+    type Key;
+
+    fn merge_from(&mut self, other: Self, conflict: Conflict) -> Result<(), Self::Key>;
+}
+
+impl<T> Merge for Vec<T> {
+    type Key = Infallible;
+
+    fn merge_from(&mut self, mut other: Self, _: Conflict) -> Result<(), Self::Key> {
+        self.append(&mut other);
+
+        Ok(())
+    }
+}
+
+impl<K, V, S> Merge for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    type Key = K;
+
+    fn merge_from(&mut self, other: Self, conflict: Conflict) -> Result<(), Self::Key> {
+        for (key, value) in other {
+            if self.contains_key(&key) {
+                match conflict {
+                    Conflict::FirstWins => continue,
+                    Conflict::LastWins => {}
+                    Conflict::Error => return Err(key),
+                }
+            }
+
+            self.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl<K, V> Merge for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+
+    fn merge_from(&mut self, other: Self, conflict: Conflict) -> Result<(), Self::Key> {
+        for (key, value) in other {
+            if self.contains_key(&key) {
+                match conflict {
+                    Conflict::FirstWins => continue,
+                    Conflict::LastWins => {}
+                    Conflict::Error => return Err(key),
+                }
+            }
+
+            self.insert(key, value);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, S> Merge for HashSet<T, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    type Key = T;
+
+    fn merge_from(&mut self, other: Self, conflict: Conflict) -> Result<(), Self::Key> {
+        for value in other {
+            if self.contains(&value) {
+                match conflict {
+                    Conflict::FirstWins => continue,
+                    Conflict::LastWins => {}
+                    Conflict::Error => return Err(value),
+                }
+            }
+
+            self.replace(value);
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Merge for Option<T> {
+    type Key = T;
+
+    fn merge_from(&mut self, other: Self, conflict: Conflict) -> Result<(), Self::Key> {
+        match (self.is_some(), other) {
+            (_, None) => Ok(()),
+            (false, other) => {
+                *self = other;
+                Ok(())
+            }
+            (true, Some(value)) => match conflict {
+                Conflict::FirstWins => Ok(()),
+                Conflict::LastWins => {
+                    *self = Some(value);
+                    Ok(())
+                }
+                Conflict::Error => Err(value),
+            },
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::fmt;
+
+pub struct Next<'a, R> {
+    run: &'a dyn Fn() -> Option<R>,
+}
+
+impl<'a, R> Next<'a, R> {
+    pub fn new(run: &'a dyn Fn() -> Option<R>) -> Self {
+        Self { run }
+    }
+
+    // Runs the rest of the chain and returns the result of its last regular
+    // implementation, or `None` when only around implementations were left.
+    pub fn run(&self) -> Option<R> {
+        (self.run)()
+    }
+}
+
+impl<R> Clone for Next<'_, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R> Copy for Next<'_, R> {}
+
+impl<R> fmt::Debug for Next<'_, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next").finish_non_exhaustive()
+    }
+}
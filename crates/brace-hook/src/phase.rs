@@ -0,0 +1,45 @@
+use std::fmt;
+
+const SPAN: i32 = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Early,
+    #[default]
+    Normal,
+    Late,
+}
+
+impl Phase {
+    pub const fn weight(self, weight: i32) -> i32 {
+        self.offset() + weight
+    }
+
+    pub const fn offset(self) -> i32 {
+        match self {
+            Phase::Early => -SPAN,
+            Phase::Normal => 0,
+            Phase::Late => SPAN,
+        }
+    }
+
+    pub const fn of(weight: i32) -> Self {
+        if weight <= -SPAN / 2 {
+            Phase::Early
+        } else if weight >= SPAN / 2 {
+            Phase::Late
+        } else {
+            Phase::Normal
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Phase::Early => "early",
+            Phase::Normal => "normal",
+            Phase::Late => "late",
+        })
+    }
+}
@@ -0,0 +1,411 @@
+use std::cmp;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use inventory::Collect;
+
+use crate::{diagnostics, scope, HookInfo};
+
+pub trait Implementation: Collect {
+    fn info(&self) -> &HookInfo;
+
+    fn is_around(&self) -> bool {
+        false
+    }
+}
+
+pub struct Registry<T: 'static> {
+    resolved: RwLock<Option<Resolved<T>>>,
+    dynamic: Mutex<Dynamic<T>>,
+}
+
+// Around implementations wrap the rest of the chain, so plain iteration only
+// sees the regular implementations.
+struct Resolved<T: 'static> {
+    chain: Arc<[Entry<T>]>,
+    hooks: Arc<[Entry<T>]>,
+}
+
+impl<T: Implementation> Resolved<T> {
+    fn new(chain: Arc<[Entry<T>]>) -> Self {
+        let hooks = chain
+            .iter()
+            .filter(|hook| !hook.is_around())
+            .cloned()
+            .collect();
+
+        Self { chain, hooks }
+    }
+}
+
+struct Dynamic<T: 'static> {
+    next: u64,
+    hooks: Vec<(u64, Arc<T>)>,
+}
+
+impl<T: 'static> Registry<T> {
+    pub const fn new() -> Self {
+        Self {
+            resolved: RwLock::new(None),
+            dynamic: Mutex::new(Dynamic {
+                next: 0,
+                hooks: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl<T: Implementation> Registry<T> {
+    pub fn hooks(&self, name: &str) -> Hooks<T> {
+        Hooks::new(self.resolved(name, |resolved| resolved.hooks.clone()))
+    }
+
+    pub fn chain(&self, name: &str) -> Hooks<T> {
+        Hooks::new(self.resolved(name, |resolved| resolved.chain.clone()))
+    }
+
+    // Every candidate implementation, including shadowed defaults and the
+    // targets of `replaces` and `disables`, in weight order.
+    pub fn registered(&self) -> Hooks<T> {
+        let dynamic = self.dynamic.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut hooks: Vec<Entry<T>> = registered(&dynamic.hooks).collect();
+
+        if let Some(overrides) = scope::current::<T>() {
+            hooks.extend(overrides.added.iter().cloned().map(Entry::Dynamic));
+        }
+
+        hooks.sort_by(|a, b| compare(a.info(), b.info()));
+
+        Hooks::new(hooks.into())
+    }
+
+    fn resolved<F>(&self, name: &str, f: F) -> Arc<[Entry<T>]>
+    where
+        F: FnOnce(&Resolved<T>) -> Arc<[Entry<T>]>,
+    {
+        if let Some(overrides) = scope::current::<T>() {
+            let dynamic = self.dynamic.lock().unwrap_or_else(PoisonError::into_inner);
+            let hooks = registered(&dynamic.hooks)
+                .chain(overrides.added.iter().cloned().map(Entry::Dynamic))
+                .filter(|hook| !overrides.is_disabled(hook))
+                .collect();
+
+            return f(&Resolved::new(resolve(name, hooks)));
+        }
+
+        if let Some(resolved) = &*self.resolved.read().unwrap_or_else(PoisonError::into_inner) {
+            return f(resolved);
+        }
+
+        let mut resolved = self
+            .resolved
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        match &*resolved {
+            Some(resolved) => f(resolved),
+            None => {
+                let dynamic = self.dynamic.lock().unwrap_or_else(PoisonError::into_inner);
+                let hooks = resolve(name, registered(&dynamic.hooks).collect());
+
+                f(resolved.insert(Resolved::new(hooks)))
+            }
+        }
+    }
+
+    fn invalidate(&self) {
+        *self
+            .resolved
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+impl<T: Implementation + Send + Sync> Registry<T> {
+    pub fn register(&'static self, hook: T) -> RegistrationHandle {
+        let mut dynamic = self.dynamic.lock().unwrap_or_else(PoisonError::into_inner);
+        let id = dynamic.next;
+
+        dynamic.next += 1;
+        dynamic.hooks.push((id, Arc::new(hook)));
+
+        drop(dynamic);
+        self.invalidate();
+
+        RegistrationHandle { registry: self, id }
+    }
+}
+
+impl<T: 'static> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+trait Unregister: Sync {
+    fn unregister(&self, id: u64);
+}
+
+impl<T: Implementation + Send + Sync> Unregister for Registry<T> {
+    fn unregister(&self, id: u64) {
+        let mut dynamic = self.dynamic.lock().unwrap_or_else(PoisonError::into_inner);
+
+        dynamic.hooks.retain(|(hook, _)| *hook != id);
+
+        drop(dynamic);
+        self.invalidate();
+    }
+}
+
+#[must_use = "the hook is unregistered when the handle is dropped"]
+pub struct RegistrationHandle {
+    registry: &'static dyn Unregister,
+    id: u64,
+}
+
+impl RegistrationHandle {
+    pub fn unregister(self) {
+        drop(self);
+    }
+}
+
+impl Drop for RegistrationHandle {
+    fn drop(&mut self) {
+        self.registry.unregister(self.id);
+    }
+}
+
+impl fmt::Debug for RegistrationHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistrationHandle")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+enum Entry<T: 'static> {
+    Static(&'static T),
+    Dynamic(Arc<T>),
+}
+
+impl<T: 'static> Clone for Entry<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Entry::Static(hook) => Entry::Static(hook),
+            Entry::Dynamic(hook) => Entry::Dynamic(hook.clone()),
+        }
+    }
+}
+
+impl<T: 'static> Deref for Entry<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Entry::Static(hook) => hook,
+            Entry::Dynamic(hook) => hook,
+        }
+    }
+}
+
+pub struct Hooks<T: 'static> {
+    hooks: Arc<[Entry<T>]>,
+    front: usize,
+    back: usize,
+}
+
+impl<T: 'static> Hooks<T> {
+    fn new(hooks: Arc<[Entry<T>]>) -> Self {
+        let back = hooks.len();
+
+        Self {
+            hooks,
+            front: 0,
+            back,
+        }
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        self.hooks[self.front..self.back].get(pos).map(Deref::deref)
+    }
+
+    pub fn peek_front(&self) -> Option<&T> {
+        self.hooks[self.front..self.back].first().map(Deref::deref)
+    }
+
+    pub fn pop_front(&mut self) -> Option<&T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+
+        Some(&self.hooks[self.front - 1])
+    }
+
+    pub fn pop_back(&mut self) -> Option<&T> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        Some(&self.hooks[self.back])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.hooks[self.front..self.back].iter().map(Deref::deref)
+    }
+
+    pub fn len(&self) -> usize {
+        self.back - self.front
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.front == self.back
+    }
+}
+
+impl<T: 'static> Clone for Hooks<T> {
+    fn clone(&self) -> Self {
+        Self {
+            hooks: self.hooks.clone(),
+            front: self.front,
+            back: self.back,
+        }
+    }
+}
+
+fn registered<T: Implementation>(dynamic: &[(u64, Arc<T>)]) -> impl Iterator<Item = Entry<T>> + '_ {
+    inventory::iter::<T>
+        .into_iter()
+        .map(Entry::Static)
+        .chain(dynamic.iter().map(|(_, hook)| Entry::Dynamic(hook.clone())))
+}
+
+fn resolve<T: Implementation>(name: &str, hooks: Vec<Entry<T>>) -> Arc<[Entry<T>]> {
+    let registered: HashSet<&'static str> = hooks.iter().map(|hook| hook.info().path()).collect();
+
+    let mut removed = HashSet::new();
+
+    for hook in &hooks {
+        let info = hook.info();
+        let targets = info.replaces().iter().map(|target| ("replaces", target));
+
+        for (action, target) in
+            targets.chain(info.disables().iter().map(|target| ("disables", target)))
+        {
+            if !registered.contains(target) {
+                diagnostics::report(format!(
+                    "implementation `{}` of hook `{}` {} `{}`, which is not registered",
+                    info.path(),
+                    name,
+                    action,
+                    target
+                ));
+            }
+
+            removed.insert(*target);
+        }
+    }
+
+    let (mut hooks, defaults): (Vec<Entry<T>>, Vec<Entry<T>>) = hooks
+        .into_iter()
+        .filter(|hook| !removed.contains(hook.info().path()))
+        .partition(|hook| !hook.info().is_default());
+
+    if hooks.is_empty() {
+        hooks = defaults;
+    }
+
+    // `inventory` hands out implementations in link order, which varies between
+    // builds, so equal weights fall back to the crate, module and function name.
+    hooks.sort_by(|a, b| compare(a.info(), b.info()));
+
+    match order(&hooks) {
+        Ok(order) => {
+            let mut hooks: Vec<Option<Entry<T>>> = hooks.into_iter().map(Some).collect();
+
+            order
+                .into_iter()
+                .map(|pos| hooks[pos].take().unwrap())
+                .collect()
+        }
+        Err(cycle) => panic!(
+            "cyclic ordering constraints between implementations of hook `{}`: {}",
+            name,
+            cycle
+                .iter()
+                .map(|pos| hooks[*pos].info().path())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        ),
+    }
+}
+
+fn compare(a: &HookInfo, b: &HookInfo) -> cmp::Ordering {
+    a.weight()
+        .cmp(&b.weight())
+        .then_with(|| a.path().split("::").cmp(b.path().split("::")))
+}
+
+// Orders the weight sorted hooks so that every `after` and `before` constraint
+// is satisfied, picking the earliest ready hook to keep weights as tie-breakers.
+fn order<T: Implementation>(hooks: &[Entry<T>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut preds = vec![Vec::new(); hooks.len()];
+
+    for (pos, hook) in hooks.iter().enumerate() {
+        for (other, target) in hooks.iter().enumerate() {
+            let (hook, target) = (hook.info(), target.info());
+
+            if hook.after().contains(&target.path()) || target.before().contains(&hook.path()) {
+                preds[pos].push(other);
+            }
+        }
+    }
+
+    let mut blocked: Vec<usize> = preds.iter().map(Vec::len).collect();
+    let mut ready: BTreeSet<usize> = (0..hooks.len()).filter(|pos| blocked[*pos] == 0).collect();
+    let mut order = Vec::with_capacity(hooks.len());
+
+    while let Some(pos) = ready.iter().next().copied() {
+        ready.remove(&pos);
+        order.push(pos);
+
+        for (next, pred) in preds.iter().enumerate() {
+            for _ in pred.iter().filter(|pred| **pred == pos) {
+                blocked[next] -= 1;
+
+                if blocked[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+    }
+
+    if order.len() == hooks.len() {
+        return Ok(order);
+    }
+
+    let mut cycle = Vec::new();
+    let mut pos = (0..hooks.len()).find(|pos| blocked[*pos] > 0).unwrap();
+
+    while !cycle.contains(&pos) {
+        cycle.push(pos);
+        pos = preds[pos]
+            .iter()
+            .copied()
+            .find(|pred| blocked[*pred] > 0)
+            .unwrap();
+    }
+
+    let start = cycle.iter().position(|item| *item == pos).unwrap();
+    let mut cycle = cycle.split_off(start);
+
+    cycle.reverse();
+    cycle.push(cycle[0]);
+
+    Err(cycle)
+}
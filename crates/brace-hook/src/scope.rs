@@ -0,0 +1,136 @@
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::Implementation;
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static NEXT: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static OVERRIDES: RefCell<HashMap<TypeId, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+pub(crate) struct Overrides<T: 'static> {
+    pub(crate) disabled: Vec<String>,
+    pub(crate) added: Vec<Arc<T>>,
+}
+
+impl<T: 'static> Overrides<T> {
+    pub(crate) fn is_disabled(&self, hook: &T) -> bool
+    where
+        T: Implementation,
+    {
+        let info = hook.info();
+
+        self.disabled
+            .iter()
+            .any(|name| name == info.name() || name == info.path())
+    }
+}
+
+pub struct Scope<T: 'static> {
+    id: u64,
+    marker: PhantomData<*const T>,
+}
+
+impl<T: Implementation> Scope<T> {
+    pub fn new() -> Self {
+        let id = NEXT.fetch_add(1, Ordering::Relaxed);
+
+        with_layers::<T, _>(|layers| {
+            layers.push((
+                id,
+                Overrides {
+                    disabled: Vec::new(),
+                    added: Vec::new(),
+                },
+            ))
+        });
+
+        ACTIVE.fetch_add(1, Ordering::SeqCst);
+
+        Self {
+            id,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn disable<N>(&mut self, name: N) -> &mut Self
+    where
+        N: Into<String>,
+    {
+        let name = name.into();
+
+        self.with_layer(|layer| layer.disabled.push(name));
+
+        self
+    }
+
+    pub fn add(&mut self, hook: T) -> &mut Self {
+        let hook = Arc::new(hook);
+
+        self.with_layer(|layer| layer.added.push(hook));
+
+        self
+    }
+
+    fn with_layer(&self, f: impl FnOnce(&mut Overrides<T>)) {
+        with_layers::<T, _>(|layers| {
+            if let Some((_, layer)) = layers.iter_mut().find(|(id, _)| *id == self.id) {
+                f(layer);
+            }
+        });
+    }
+}
+
+impl<T: Implementation> Default for Scope<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: 'static> Drop for Scope<T> {
+    fn drop(&mut self) {
+        with_layers::<T, _>(|layers| layers.retain(|(id, _)| *id != self.id));
+
+        ACTIVE.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub(crate) fn current<T: 'static>() -> Option<Overrides<T>> {
+    if ACTIVE.load(Ordering::SeqCst) == 0 {
+        return None;
+    }
+
+    with_layers::<T, _>(|layers| {
+        if layers.is_empty() {
+            return None;
+        }
+
+        Some(Overrides {
+            disabled: layers
+                .iter()
+                .flat_map(|(_, layer)| layer.disabled.iter().cloned())
+                .collect(),
+            added: layers
+                .iter()
+                .flat_map(|(_, layer)| layer.added.iter().cloned())
+                .collect(),
+        })
+    })
+}
+
+fn with_layers<T: 'static, R>(f: impl FnOnce(&mut Vec<(u64, Overrides<T>)>) -> R) -> R {
+    OVERRIDES.with(|overrides| {
+        let mut overrides = overrides.borrow_mut();
+        let layers = overrides
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Vec::<(u64, Overrides<T>)>::new()));
+
+        f(layers.downcast_mut().unwrap())
+    })
+}
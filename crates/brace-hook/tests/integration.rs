@@ -1,5 +1,21 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Waker};
+
 use brace_hook::{hook, register};
 
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = Box::pin(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+            return res;
+        }
+    }
+}
+
 #[hook]
 fn my_hook(input: &str) -> String {}
 
@@ -27,9 +43,51 @@ fn test_hook_registration() {
 
     let res: Vec<String> = res.collect();
 
-    assert!(res.contains(&String::from("hook_1: hello")));
-    assert!(res.contains(&String::from("hook_2: hello")));
-    assert!(res.contains(&String::from("hook_3: hello")));
+    assert_eq!(res, vec!["hook_1: hello", "hook_2: hello", "hook_3: hello"]);
+}
+
+#[hook]
+fn tied() -> &'static str {}
+
+mod tied_b {
+    use brace_hook::hook;
+
+    #[hook(super::tied)]
+    fn implementation() -> &'static str {
+        "tied_b::implementation"
+    }
+}
+
+mod tied_a {
+    use brace_hook::hook;
+
+    #[hook(super::tied)]
+    fn zeta() -> &'static str {
+        "tied_a::zeta"
+    }
+
+    #[hook(super::tied)]
+    fn alpha() -> &'static str {
+        "tied_a::alpha"
+    }
+}
+
+#[hook(tied)]
+fn tied_root() -> &'static str {
+    "tied_root"
+}
+
+#[test]
+fn test_hook_tie_breaking() {
+    assert_eq!(
+        tied::with().collect::<Vec<_>>(),
+        vec![
+            "tied_a::alpha",
+            "tied_a::zeta",
+            "tied_b::implementation",
+            "tied_root"
+        ]
+    );
 }
 
 #[hook]
@@ -87,6 +145,145 @@ fn test_hook_with_weights() {
     assert_eq!(res.next(), None);
 }
 
+#[test]
+fn test_hook_with_weights_cached() {
+    let first: Vec<&str> = weighted::with().collect();
+    let second: Vec<&str> = weighted::with().collect();
+
+    assert_eq!(first, vec!["d", "b", "c", "a"]);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_hook_implementations() {
+    let res: Vec<(&str, i32)> = weighted::implementations()
+        .map(|info| (info.name(), info.weight()))
+        .collect();
+
+    assert_eq!(
+        res,
+        vec![
+            ("weighted_d", -50),
+            ("weighted_b", 0),
+            ("weighted_c", 20),
+            ("weighted_a", 300),
+        ]
+    );
+
+    let info = weighted::implementations().next().unwrap();
+
+    assert_eq!(info.path(), "integration::weighted_d");
+    assert_eq!(info.module_path(), "integration");
+    assert_eq!(info.file(), Some(file!()));
+    assert!(info.line().is_some());
+    assert!(!info.is_default());
+}
+
+#[test]
+fn test_hook_implementations_registered() {
+    let mut res: Vec<&str> = my_hook::implementations().map(|info| info.path()).collect();
+
+    res.sort_unstable();
+
+    assert_eq!(
+        res,
+        vec![
+            "integration::hook_1",
+            "integration::hook_2",
+            "integration::hook_3",
+        ]
+    );
+    assert!(my_hook::implementations().all(|info| info.file() == Some(file!())));
+}
+
+#[test]
+fn test_hook_implementations_default() {
+    let res: Vec<_> = hook_with_default::implementations().collect();
+
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].name(), "hook_with_default__default");
+    assert!(res[0].is_default());
+}
+
+#[test]
+fn test_hook_catalog() {
+    let res = brace_hook::catalog()
+        .find(|hook| hook.name() == "weighted")
+        .unwrap();
+
+    assert_eq!(res.module_path(), "integration");
+    assert_eq!(res.signature(), "fn weighted() -> &'static str");
+    assert_eq!(res.implementations().count(), 4);
+
+    let res = brace_hook::catalog()
+        .find(|hook| hook.name() == "mutate")
+        .unwrap();
+
+    assert_eq!(res.signature(), "fn mutate(items: &mut Vec<&str>)");
+
+    let res = brace_hook::catalog()
+        .find(|hook| hook.name() == "async_hook")
+        .unwrap();
+
+    assert_eq!(
+        res.signature(),
+        "async fn async_hook(a: &str, b: &str) -> String"
+    );
+
+    let res = brace_hook::catalog()
+        .find(|hook| hook.name() == "visibility")
+        .unwrap();
+
+    assert_eq!(res.module_path(), "integration::nested");
+    assert_eq!(res.signature(), "fn visibility()");
+    assert_eq!(res.implementations().count(), 2);
+}
+
+#[hook]
+fn ordered() -> &'static str {}
+
+#[hook(ordered, 0, after = ordered_c)]
+fn ordered_a() -> &'static str {
+    "a"
+}
+
+#[hook(ordered, 1)]
+fn ordered_b() -> &'static str {
+    "b"
+}
+
+#[hook(ordered, 10)]
+fn ordered_c() -> &'static str {
+    "c"
+}
+
+#[hook(ordered, 20, before = ordered_b)]
+fn ordered_d() -> &'static str {
+    "d"
+}
+
+#[test]
+fn test_hook_with_ordering() {
+    let res: Vec<&str> = ordered::with().collect();
+
+    assert_eq!(res, vec!["c", "a", "d", "b"]);
+}
+
+#[hook]
+fn cyclic() {}
+
+#[hook(cyclic, after = cyclic_b)]
+fn cyclic_a() {}
+
+#[hook(cyclic, after = cyclic_a)]
+fn cyclic_b() {}
+
+#[test]
+#[should_panic(expected = "cyclic ordering constraints between implementations of hook `cyclic`")]
+fn test_hook_with_cyclic_ordering() {
+    cyclic::with();
+}
+
 #[hook]
 fn mutate(items: &mut Vec<&str>) {}
 
@@ -193,3 +390,805 @@ fn test_hook_with_default_unused() {
 
     assert_eq!(res[0], "b: two, a: one");
 }
+
+#[hook]
+async fn async_hook(a: &str, b: &str) -> String {}
+
+#[hook(async_hook, 2)]
+async fn async_hook_1(a: &str, b: &str) -> String {
+    format!("async_hook_1: {} {}", a, b)
+}
+
+#[hook(async_hook, 1)]
+async fn async_hook_2(a: &str, b: &str) -> String {
+    format!("async_hook_2: {} {}", a, b)
+}
+
+#[test]
+fn test_async_hook() {
+    let a = String::from("one");
+    let res = block_on(brace_hook::invoke_async(async_hook::with(&a, "two")));
+
+    assert_eq!(res, vec!["async_hook_2: one two", "async_hook_1: one two"]);
+
+    let res: Vec<&str> = async_hook::implementations()
+        .map(|info| info.name())
+        .collect();
+
+    assert_eq!(res, vec!["async_hook_2", "async_hook_1"]);
+}
+
+#[hook]
+async fn async_hook_with_default() -> &'static str {
+    "default"
+}
+
+#[test]
+fn test_async_hook_with_default() {
+    let res = block_on(brace_hook::join(async_hook_with_default::with()));
+
+    assert_eq!(res, vec!["default"]);
+}
+
+#[hook]
+fn dynamic(input: &str) -> String {}
+
+#[hook(dynamic, 10)]
+fn dynamic_static(input: &str) -> String {
+    format!("static: {}", input)
+}
+
+#[test]
+fn test_hook_runtime_registration() {
+    let before = dynamic::with("hello");
+
+    let early = dynamic::register(|input| format!("early: {}", input), 5);
+    let late = dynamic::register(|input| format!("late: {}", input), 20);
+
+    assert_eq!(before.collect::<Vec<_>>(), vec!["static: hello"]);
+
+    let during = dynamic::with("hello");
+
+    assert_eq!(
+        dynamic::with("hello").collect::<Vec<_>>(),
+        vec!["early: hello", "static: hello", "late: hello"]
+    );
+
+    early.unregister();
+
+    assert_eq!(
+        dynamic::with("hello").collect::<Vec<_>>(),
+        vec!["static: hello", "late: hello"]
+    );
+
+    drop(late);
+
+    assert_eq!(
+        dynamic::with("hello").collect::<Vec<_>>(),
+        vec!["static: hello"]
+    );
+    assert_eq!(during.len(), 3);
+}
+
+#[hook]
+fn dynamic_with_default() -> &'static str {
+    "default"
+}
+
+#[test]
+fn test_hook_runtime_registration_with_default() {
+    assert_eq!(
+        dynamic_with_default::with().collect::<Vec<_>>(),
+        vec!["default"]
+    );
+
+    let handle = std::thread::spawn(|| dynamic_with_default::register(|| "dynamic", 0))
+        .join()
+        .unwrap();
+
+    assert_eq!(
+        dynamic_with_default::with().collect::<Vec<_>>(),
+        vec!["dynamic"]
+    );
+
+    drop(handle);
+
+    assert_eq!(
+        dynamic_with_default::with().collect::<Vec<_>>(),
+        vec!["default"]
+    );
+}
+
+#[hook]
+fn scoped(input: &str) -> String {}
+
+#[hook(scoped, 1)]
+fn scoped_1(input: &str) -> String {
+    format!("scoped_1: {}", input)
+}
+
+#[hook(scoped, 2)]
+fn scoped_2(input: &str) -> String {
+    format!("scoped_2: {}", input)
+}
+
+#[test]
+fn test_hook_override_scope() {
+    let res = scoped::override_scope(|scope| {
+        scope
+            .disable("scoped_1")
+            .add(|input| format!("fake: {}", input), 5);
+
+        scoped::with("hello").collect::<Vec<_>>()
+    });
+
+    assert_eq!(res, vec!["scoped_2: hello", "fake: hello"]);
+    assert_eq!(
+        scoped::with("hello").collect::<Vec<_>>(),
+        vec!["scoped_1: hello", "scoped_2: hello"]
+    );
+}
+
+#[test]
+fn test_hook_override_scope_guard() {
+    let mut outer = scoped::scope();
+
+    outer.disable("integration::scoped_2");
+
+    {
+        let mut inner = scoped::scope();
+
+        inner.disable("scoped_1");
+
+        assert_eq!(scoped::with("hello").len(), 0);
+    }
+
+    assert_eq!(
+        scoped::with("hello").collect::<Vec<_>>(),
+        vec!["scoped_1: hello"]
+    );
+
+    let other = std::thread::spawn(|| scoped::with("hello").len())
+        .join()
+        .unwrap();
+
+    assert_eq!(other, 2);
+
+    drop(outer);
+
+    assert_eq!(scoped::with("hello").len(), 2);
+}
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn test_hook_thread_safety() {
+    assert_send_sync::<my_hook>();
+    assert_send_sync::<mutate>();
+    assert_send_sync::<async_hook>();
+
+    assert_send(&my_hook::with("hello"));
+    assert_send(&mutate::with(&mut Vec::new()));
+    assert_send(&async_hook::with("a", "b"));
+    assert_send(&async_hook::with("a", "b").next().unwrap());
+}
+
+#[test]
+fn test_hook_concurrent_invocation() {
+    std::thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut items = Vec::new();
+
+                    mutate::with(&mut items).for_each(drop);
+
+                    (my_hook::with("hello").collect::<Vec<_>>(), items)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            let (res, items) = handle.join().unwrap();
+
+            assert_eq!(res.len(), 3);
+            assert_eq!(items, vec!["mutate 1", "mutate 2", "mutate 3"]);
+        }
+    });
+}
+
+#[hook]
+#[hook_attr(local)]
+fn local(input: &str) -> String {}
+
+#[hook(local, 1)]
+fn local_1(input: &str) -> String {
+    format!("local_1: {}", input)
+}
+
+#[test]
+fn test_local_hook() {
+    let prefix = std::rc::Rc::new(String::from("rc"));
+
+    let res = local::override_scope(|scope| {
+        let prefix = prefix.clone();
+
+        scope.add(move |input| format!("{}: {}", prefix, input), 2);
+
+        local::with("hello").collect::<Vec<_>>()
+    });
+
+    assert_eq!(res, vec!["local_1: hello", "rc: hello"]);
+
+    let other = std::thread::spawn(|| local::with("hello").collect::<Vec<_>>())
+        .join()
+        .unwrap();
+
+    assert_eq!(other, vec!["local_1: hello"]);
+}
+
+pub struct Page {
+    suffix: &'static str,
+}
+
+#[hook]
+#[hook_attr(pipeline)]
+fn filter_title(title: String, ctx: &Page) -> String {}
+
+#[hook(filter_title, 1)]
+fn filter_title_trim(title: String, _: &Page) -> String {
+    title.trim().to_owned()
+}
+
+#[hook(filter_title, 2)]
+fn filter_title_suffix(title: String, ctx: &Page) -> String {
+    format!("{}{}", title, ctx.suffix)
+}
+
+#[hook]
+fn pipeline() -> &'static str {}
+
+#[hook(pipeline)]
+fn pipeline_1() -> &'static str {
+    "pipeline_1"
+}
+
+#[test]
+fn test_pipeline_hook() {
+    let ctx = Page { suffix: " | Site" };
+
+    assert_eq!(
+        filter_title::pipeline(String::from("  Hello "), &ctx),
+        "Hello | Site"
+    );
+    assert_eq!(
+        filter_title::with(String::from(" Hello"), &ctx).collect::<Vec<_>>(),
+        vec!["Hello", " Hello | Site"]
+    );
+    assert_eq!(pipeline::with().collect::<Vec<_>>(), vec!["pipeline_1"]);
+}
+
+pub struct Request {
+    path: &'static str,
+    log: std::sync::Mutex<Vec<String>>,
+}
+
+#[hook]
+fn handle_request(req: &Request) -> String {}
+
+#[hook(handle_request, 10)]
+fn handle_request_page(req: &Request) -> String {
+    req.log.lock().unwrap().push(String::from("page"));
+    format!("page {}", req.path)
+}
+
+#[hook(handle_request)]
+fn handle_request_fallback(req: &Request) -> String {
+    req.log.lock().unwrap().push(String::from("fallback"));
+    format!("fallback {}", req.path)
+}
+
+#[hook(handle_request, 1, around)]
+fn handle_request_timing(req: &Request, next: brace_hook::Next<'_, String>) -> String {
+    req.log.lock().unwrap().push(String::from("before"));
+
+    let res = next.run().unwrap_or_default();
+
+    req.log.lock().unwrap().push(String::from("after"));
+
+    format!("[{}]", res)
+}
+
+#[hook(handle_request, 2, around)]
+fn handle_request_guard(req: &Request, next: brace_hook::Next<'_, String>) -> String {
+    match req.path {
+        "/admin" => String::from("denied"),
+        "/retry" => {
+            next.run();
+            next.run().unwrap_or_default()
+        }
+        _ => next.run().unwrap_or_default(),
+    }
+}
+
+#[hook]
+fn handle_ping() -> String {}
+
+#[hook(handle_ping, around)]
+fn handle_ping_pong(next: brace_hook::Next<'_, String>) -> String {
+    next.run().unwrap_or_else(|| String::from("pong"))
+}
+
+#[test]
+fn test_around_hook() {
+    let request = |path| Request {
+        path,
+        log: std::sync::Mutex::new(Vec::new()),
+    };
+
+    let req = request("/");
+
+    assert_eq!(handle_request::handle(&req), Some(String::from("[page /]")));
+    assert_eq!(
+        *req.log.lock().unwrap(),
+        vec!["fallback", "before", "page", "after"]
+    );
+
+    let req = request("/admin");
+
+    assert_eq!(handle_request::handle(&req), Some(String::from("[denied]")));
+    assert_eq!(
+        *req.log.lock().unwrap(),
+        vec!["fallback", "before", "after"]
+    );
+
+    let req = request("/retry");
+
+    assert_eq!(
+        handle_request::handle(&req),
+        Some(String::from("[page /retry]"))
+    );
+    assert_eq!(
+        *req.log.lock().unwrap(),
+        vec!["fallback", "before", "page", "page", "after"]
+    );
+
+    assert_eq!(
+        handle_request::with(&req).collect::<Vec<_>>(),
+        vec!["fallback /retry", "page /retry"]
+    );
+    assert_eq!(
+        handle_request::implementations()
+            .map(|info| (info.name().to_string(), info.weight()))
+            .collect::<Vec<_>>(),
+        vec![
+            (String::from("handle_request_fallback"), 0),
+            (String::from("handle_request_timing"), 1),
+            (String::from("handle_request_guard"), 2),
+            (String::from("handle_request_page"), 10),
+        ]
+    );
+
+    let req = request("/admin");
+    let res = handle_request::override_scope(|scope| {
+        scope.disable("handle_request_guard");
+        handle_request::handle(&req)
+    });
+
+    assert_eq!(res, Some(String::from("[page /admin]")));
+    assert_eq!(handle_ping::handle(), Some(String::from("pong")));
+    assert_eq!(unused::with().handle(), None);
+}
+
+#[hook]
+#[hook_attr(alterable)]
+fn permissions() -> Vec<&'static str> {}
+
+#[hook(permissions, 1)]
+fn permissions_node() -> Vec<&'static str> {
+    vec!["create node", "delete node"]
+}
+
+#[hook(permissions, 2)]
+fn permissions_user() -> Vec<&'static str> {
+    vec!["create user"]
+}
+
+#[hook(permissions_alter)]
+fn permissions_alter_node(permissions: &mut Vec<&'static str>) {
+    permissions.retain(|permission| *permission != "delete node");
+}
+
+#[test]
+fn test_alterable_hook() {
+    assert_eq!(
+        permissions::with().collect::<Vec<_>>(),
+        vec![vec!["create node", "delete node"], vec!["create user"]]
+    );
+    assert_eq!(permissions::collect(), vec!["create node", "create user"]);
+    assert_eq!(permissions_alter::implementations().count(), 1);
+}
+
+#[derive(Default)]
+pub struct Strict(Vec<&'static str>);
+
+impl brace_hook::Merge for Strict {
+    type Key = &'static str;
+
+    fn merge_from(&mut self, other: Self, _: brace_hook::Conflict) -> Result<(), Self::Key> {
+        match other.0.iter().find(|item| self.0.contains(item)) {
+            Some(item) => Err(item),
+            None => {
+                self.0.extend(other.0);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[hook]
+#[hook_attr(alterable)]
+fn strict() -> Strict {}
+
+#[hook(strict, 1)]
+fn strict_1() -> Strict {
+    Strict(vec!["a"])
+}
+
+#[hook(strict, 2)]
+fn strict_2() -> Strict {
+    Strict(vec!["a"])
+}
+
+#[test]
+#[should_panic(
+    expected = "implementation `integration::strict_2` of hook `strict` failed \
+                           to merge `Strict` with `Conflict::LastWins`"
+)]
+fn test_alterable_hook_conflict() {
+    strict::collect();
+}
+
+static MENU_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[hook]
+#[hook_attr(cache)]
+fn menu() -> &'static str {}
+
+#[hook(menu)]
+fn menu_main() -> &'static str {
+    MENU_CALLS.fetch_add(1, Ordering::SeqCst);
+    "main"
+}
+
+#[hook]
+#[hook_attr(cache)]
+fn menu_items(depth: u32) -> u32 {}
+
+#[hook(menu_items)]
+fn menu_items_main(depth: u32) -> u32 {
+    MENU_CALLS.fetch_add(1, Ordering::SeqCst);
+    depth * 2
+}
+
+#[test]
+fn test_cached_hook() {
+    let calls = || MENU_CALLS.load(Ordering::SeqCst);
+
+    assert_eq!(*menu::cached(), vec!["main"]);
+    assert_eq!(*menu::cached(), vec!["main"]);
+    assert!(Arc::ptr_eq(&menu::cached(), &menu::cached()));
+    assert_eq!(calls(), 1);
+
+    assert_eq!(*menu_items::cached(1), vec![2]);
+    assert_eq!(*menu_items::cached(2), vec![4]);
+    assert_eq!(*menu_items::cached(1), vec![2]);
+    assert_eq!(calls(), 3);
+
+    menu::invalidate();
+
+    assert_eq!(*menu::cached(), vec!["main"]);
+    assert_eq!(*menu_items::cached(2), vec![4]);
+    assert_eq!(calls(), 4);
+
+    brace_hook::invalidate_all();
+
+    assert_eq!(*menu::cached(), vec!["main"]);
+    assert_eq!(*menu_items::cached(2), vec![4]);
+    assert_eq!(calls(), 6);
+}
+
+#[test]
+fn test_cache_invalidated_while_computing() {
+    static CACHE: brace_hook::Cache<u32, &'static str> = brace_hook::Cache::new();
+
+    let value = CACHE.get_or_insert_with(1, || {
+        CACHE.invalidate();
+        "stale"
+    });
+
+    assert_eq!(*value, "stale");
+    assert_eq!(*CACHE.get_or_insert_with(1, || "fresh"), "fresh");
+    assert_eq!(*CACHE.get_or_insert_with(1, || "again"), "fresh");
+}
+
+#[test]
+fn test_cache_filled_while_invalidating_all() {
+    let caches = (0..1000)
+        .map(|_| &*Box::leak(Box::new(brace_hook::Cache::<u32, u32>::new())))
+        .collect::<Vec<_>>();
+
+    let invalidate = std::thread::spawn(|| {
+        for _ in 0..1000 {
+            brace_hook::invalidate_all();
+        }
+    });
+
+    for cache in caches {
+        assert_eq!(*cache.get_or_insert_with(1, || 2), 2);
+    }
+
+    invalidate.join().unwrap();
+}
+
+#[hook]
+fn keyword() -> &'static str {}
+
+#[hook(keyword, weight = 10, name = "second", tags("admin", "audit"))]
+fn keyword_a() -> &'static str {
+    "a"
+}
+
+#[hook(keyword, weight = -10)]
+fn keyword_b() -> &'static str {
+    "b"
+}
+
+#[hook(keyword, default)]
+fn keyword_fallback() -> &'static str {
+    "fallback"
+}
+
+#[test]
+fn test_hook_keyword_arguments() {
+    assert_eq!(keyword::with().collect::<Vec<_>>(), vec!["b", "a"]);
+
+    let infos = keyword::implementations().collect::<Vec<_>>();
+
+    assert_eq!(infos[1].name(), "second");
+    assert_eq!(infos[1].path(), "integration::keyword_a");
+    assert_eq!(infos[1].weight(), 10);
+    assert_eq!(infos[1].tags(), ["admin", "audit"]);
+    assert!(infos[1].has_tag("admin"));
+    assert!(!infos[0].has_tag("admin"));
+
+    let res = keyword::override_scope(|scope| {
+        scope.disable("second").disable("keyword_b");
+
+        keyword::with().collect::<Vec<_>>()
+    });
+
+    assert_eq!(res, vec!["fallback"]);
+    assert_eq!(
+        keyword::registered()
+            .map(|(info, active)| (info.name(), active))
+            .collect::<Vec<_>>(),
+        vec![
+            ("keyword_b", true),
+            ("keyword_fallback", false),
+            ("second", true)
+        ]
+    );
+}
+
+mod weights {
+    pub const LATE: i32 = 100;
+}
+
+const EARLY: i32 = -100;
+
+const fn late(offset: i32) -> i32 {
+    weights::LATE + offset
+}
+
+#[hook]
+fn phased() -> &'static str {}
+
+#[hook(phased, weight = weights::LATE + 5)]
+fn phased_a() -> &'static str {
+    "a"
+}
+
+#[hook(phased, phase = Late, weight = -10)]
+fn phased_b() -> &'static str {
+    "b"
+}
+
+#[hook(phased, weight = brace_hook::Phase::Early.weight(50))]
+fn phased_c() -> &'static str {
+    "c"
+}
+
+#[hook(phased, weight = weights::LATE * 2)]
+fn phased_d() -> &'static str {
+    "d"
+}
+
+#[hook(phased, weight = EARLY)]
+fn phased_e() -> &'static str {
+    "e"
+}
+
+#[hook(phased, weight = late(50))]
+fn phased_f() -> &'static str {
+    "f"
+}
+
+#[test]
+fn test_hook_with_phases() {
+    assert_eq!(
+        phased::with().collect::<Vec<_>>(),
+        vec!["c", "e", "a", "f", "d", "b"]
+    );
+
+    let infos = phased::implementations().collect::<Vec<_>>();
+
+    assert_eq!(infos[0].phase(), brace_hook::Phase::Early);
+    assert_eq!(infos[0].phase_weight(), 50);
+    assert_eq!(infos[1].weight(), -100);
+    assert_eq!(infos[2].phase(), brace_hook::Phase::Normal);
+    assert_eq!(infos[2].weight(), 105);
+    assert_eq!(infos[3].weight(), 150);
+    assert_eq!(infos[5].phase(), brace_hook::Phase::Late);
+    assert_eq!(infos[5].phase_weight(), -10);
+}
+
+#[hook]
+fn on_create(id: u32) -> String {}
+
+#[hook]
+fn on_update(id: u32) -> String {}
+
+#[hook(on_create, on_update, weight = 5)]
+fn clear_cache(id: u32) -> String {
+    format!("clear {}", id)
+}
+
+fn log_change(id: u32) -> String {
+    format!("log {}", id)
+}
+
+register!([on_create, on_update], log_change, 10);
+
+#[test]
+fn test_hook_multiple_targets() {
+    assert_eq!(
+        on_create::with(1).collect::<Vec<_>>(),
+        vec!["clear 1", "log 1"]
+    );
+    assert_eq!(
+        on_update::with(2).collect::<Vec<_>>(),
+        vec!["clear 2", "log 2"]
+    );
+    assert_eq!(
+        on_update::implementations()
+            .map(|info| info.weight())
+            .collect::<Vec<_>>(),
+        vec![5, 10]
+    );
+}
+
+#[hook]
+fn render_block(id: u32) -> String {}
+
+mod upstream {
+    use brace_hook::hook;
+
+    #[hook(super::render_block, 1)]
+    pub fn header(id: u32) -> String {
+        format!("upstream header {}", id)
+    }
+
+    #[hook(super::render_block, 2)]
+    pub fn sidebar(id: u32) -> String {
+        format!("upstream sidebar {}", id)
+    }
+
+    #[hook(super::render_block, 3)]
+    pub fn footer(id: u32) -> String {
+        format!("upstream footer {}", id)
+    }
+
+    pub fn unregistered(id: u32) -> String {
+        format!("unregistered {}", id)
+    }
+}
+
+#[hook(render_block, 10, replaces = upstream::header)]
+fn render_block_header(id: u32) -> String {
+    format!("header {}", id)
+}
+
+#[hook(render_block, disables = upstream::sidebar, replaces = upstream::unregistered)]
+fn render_block_cleanup(id: u32) -> String {
+    format!("cleanup {}", id)
+}
+
+#[test]
+fn test_hook_replaces_and_disables() {
+    assert_eq!(
+        render_block::with(1).collect::<Vec<_>>(),
+        vec!["cleanup 1", "upstream footer 1", "header 1"]
+    );
+    assert_eq!(
+        render_block::implementations()
+            .map(|info| info.weight())
+            .collect::<Vec<_>>(),
+        vec![0, 3, 10]
+    );
+    assert_eq!(
+        render_block::registered()
+            .map(|(info, active)| (info.name(), active))
+            .collect::<Vec<_>>(),
+        vec![
+            ("render_block_cleanup", true),
+            ("header", false),
+            ("sidebar", false),
+            ("footer", true),
+            ("render_block_header", true),
+        ]
+    );
+
+    assert!(brace_hook::diagnostics().contains(&String::from(
+        "implementation `integration::render_block_cleanup` of hook `render_block` replaces \
+         `integration::upstream::unregistered`, which is not registered"
+    )));
+}
+
+#[hook]
+fn unchecked() {}
+
+#[hook(unchecked, replaces = upstream::footer)]
+fn unchecked_footer() {}
+
+#[test]
+fn test_hook_check() {
+    let message = String::from(
+        "implementation `integration::unchecked_footer` of hook `unchecked` replaces \
+         `integration::upstream::footer`, which is not registered",
+    );
+
+    assert!(!brace_hook::diagnostics().contains(&message));
+
+    let diagnostics = brace_hook::check();
+
+    assert!(diagnostics.contains(&message));
+    assert!(diagnostics.iter().any(|diagnostic| diagnostic
+        .starts_with("cyclic ordering constraints between implementations of hook `cyclic`")));
+}
+
+#[test]
+fn test_hook_override_scope_out_of_order() {
+    let mut outer = scoped::scope();
+    let mut inner = scoped::scope();
+
+    outer.disable("scoped_1");
+    inner.disable("scoped_2");
+
+    assert_eq!(scoped::with("hello").len(), 0);
+
+    drop(outer);
+
+    inner.add(|input| format!("fake: {}", input), 5);
+
+    assert_eq!(
+        scoped::with("hello").collect::<Vec<_>>(),
+        vec!["scoped_1: hello", "fake: hello"]
+    );
+
+    drop(inner);
+
+    assert_eq!(scoped::with("hello").len(), 2);
+}